@@ -1,3 +1,6 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(unused_doc_comments)]
+
 fn main() {
     // 这个是单行注释的例子
     // 使用双正斜线开头
@@ -0,0 +1,6 @@
+use rbe::snapshot::Snapshot;
+
+#[test]
+fn snapshot() {
+    Snapshot::new(env!("CARGO_BIN_EXE_Comments")).check();
+}
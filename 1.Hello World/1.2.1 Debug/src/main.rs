@@ -1,3 +1,6 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(dead_code)]
+
 // 这个结构体不能被 `fmt::Display` 或 `fmt::Debug` 打印
 struct UnPrintable(i32);
 
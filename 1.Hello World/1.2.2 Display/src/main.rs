@@ -1,3 +1,6 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(unused_imports)]
+
 use std::fmt::write;
 
 fn example01() {
@@ -81,8 +84,11 @@ fn example02() {
     // println!("What does Point2D look like in binary: {:b}?", point);
 }
 
+rbe::sections! {
+    example01: "为结构体实现 `fmt::Display`",
+    example02: "`fmt::Display` 和 `fmt::Debug` 的对比",
+}
+
 fn main() {
-    example01();
-    example02();
-    println!("Hello, world!");
+    rbe::run(SECTIONS);
 }
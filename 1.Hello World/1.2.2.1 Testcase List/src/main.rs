@@ -1,32 +1,153 @@
 use std::fmt;
 
-// 定义 `List` 结构体，内部包含一个 `Vec`
-struct List(Vec<i32>);
+// 定义 `List` 结构体，内部包含一个 `Vec`，以及输出时使用的括号和分隔符。
+// 泛型 `T` 表示列表可以存放任意类型的数据，只要在输出的时候这个类型实现了 `fmt::Display` 就可以。
+struct List<T> {
+    items: Vec<T>,
+    // 开始括号和结束括号
+    open: &'static str,
+    close: &'static str,
+    // 元素之间的分隔符
+    separator: &'static str,
+    // 是否在每个元素前面输出元素的索引，例如 `0: 1, 1: 2`
+    indexed: bool,
+}
+
+impl<T> List<T> {
+    // 默认的输出格式和 `Vec` 的 `Debug` 输出一样，`[1, 2, 3]`
+    fn new(items: Vec<T>) -> Self {
+        List {
+            items,
+            open: "[",
+            close: "]",
+            separator: ", ",
+            indexed: false,
+        }
+    }
+
+    // 下面这几个方法都会消耗掉 `self` 然后返回修改后的 `self`，这样就可以链式调用
+    fn delimiters(mut self, open: &'static str, close: &'static str) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+
+    fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    fn indexed(mut self, indexed: bool) -> Self {
+        self.indexed = indexed;
+        self
+    }
+}
+
+impl<T> From<Vec<T>> for List<T> {
+    fn from(items: Vec<T>) -> Self {
+        List::new(items)
+    }
+}
 
-impl fmt::Display for List {
+impl<T: fmt::Display> fmt::Display for List<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // 通过元组的索引，获取内部 `Vec` 的引用
-        let vec = &self.0;
+        // `{:#}` 会设置 `alternate` 标记，这个时候每个元素单独占一行并且缩进四个空格
+        let pretty = f.alternate();
 
         // 不管有没有数据都要先打印一个开始括号
         // `?` 操作符表示如果 `write!` 宏出错了，则结束当前的函数，并把错误返回给调用方。
-        write!(f, "[")?;
+        f.write_str(self.open)?;
 
         // 使用 `Vec` 的 `iter()` 方法获取值引用的迭代器，再使用迭代器 `enumerate()` 返回一个带有计数器的迭代器
-        for (count, value) in vec.iter().enumerate() {
-            // 除了第一个值以外的所有值都先在前面打印一个 `", "` 分隔符
-            if count != 0 {
-                write!(f, ", ")?;
+        for (count, value) in self.items.iter().enumerate() {
+            if pretty {
+                // 多行格式下每个元素单独占一行
+                f.write_str("\n    ")?;
+            } else if count != 0 {
+                // 除了第一个值以外的所有值都先在前面打印一个分隔符
+                f.write_str(self.separator)?;
+            }
+
+            if self.indexed {
+                // `write!` 会创建新的格式化参数，所以这里不会受到 `{:5}` 这类宽度设置的影响
+                write!(f, "{}: ", count)?;
+            }
+
+            // 直接把当前的 `Formatter` 交给元素自己格式化，
+            // 这样 `{:>5}` `{:.2}` 这类宽度、精度、对齐的设置就会原样的应用到每一个元素上
+            fmt::Display::fmt(value, f)?;
+
+            if pretty {
+                // 多行格式下每个元素后面都跟着一个去掉空白的分隔符，例如 `,`
+                f.write_str(self.separator.trim_end())?;
             }
-            // 然后继续打印当前的数据
-            write!(f, "{}", value)?;
         }
+
+        if pretty && !self.items.is_empty() {
+            f.write_str("\n")?;
+        }
+
         // 最后补全结束的括号
-        write!(f, "]")
+        f.write_str(self.close)
     }
 }
 
 fn main() {
-    let v = List(vec![1, 2, 3]);
+    let v = List::new(vec![1, 2, 3]);
     println!("{}", v);
+
+    // 带有索引的输出
+    println!("{}", List::new(vec![1, 2, 3]).indexed(true));
+
+    // 自定义括号和分隔符
+    let words = List::new(vec!["error", "warn", "info"])
+        .delimiters("<", ">")
+        .separator(" | ");
+    println!("{}", words);
+
+    // 宽度和精度会应用到每一个元素上
+    let floats: List<f64> = vec![0.125, 2.5, 10.75].into();
+    println!("{:.2}", floats);
+    println!("{:>6.1}", floats);
+
+    // 多行输出
+    println!("{:#}", List::new(vec![1, 2, 3]).indexed(true));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_layout() {
+        assert_eq!(List::new(vec![1, 2, 3]).to_string(), "[1, 2, 3]");
+        assert_eq!(List::<i32>::new(vec![]).to_string(), "[]");
+    }
+
+    #[test]
+    fn indexed_with_custom_delimiters() {
+        let list = List::new(vec!['a', 'b'])
+            .indexed(true)
+            .delimiters("(", ")")
+            .separator("; ");
+        assert_eq!(list.to_string(), "(0: a; 1: b)");
+    }
+
+    #[test]
+    fn width_and_precision_apply_to_items() {
+        let list = List::new(vec![1.0, 22.25]);
+        assert_eq!(format!("{:.1}", list), "[1.0, 22.2]");
+        assert_eq!(format!("{:>5}", list), "[    1, 22.25]");
+        assert_eq!(
+            format!("{:<3}", List::new(vec!["a"]).indexed(true)),
+            "[0: a  ]"
+        );
+    }
+
+    #[test]
+    fn alternate_is_multi_line() {
+        let list = List::new(vec![1, 2]).indexed(true);
+        assert_eq!(format!("{:#}", list), "[\n    0: 1,\n    1: 2,\n]");
+        assert_eq!(format!("{:#}", List::<i32>::new(vec![])), "[]");
+    }
 }
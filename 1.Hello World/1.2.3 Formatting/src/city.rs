@@ -0,0 +1,310 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// 地球的平均半径，单位是千米
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct City {
+    pub name: String,
+    // 纬度，北纬为正，南纬为负
+    pub lat: f64,
+    // 经度，东经为正，西经为负
+    pub lon: f64,
+}
+
+impl City {
+    pub fn new(name: impl Into<String>, lat: f64, lon: f64) -> Self {
+        City {
+            name: name.into(),
+            lat,
+            lon,
+        }
+    }
+
+    /// 使用半正矢公式（haversine）计算两个城市之间的大圆距离，单位是千米
+    pub fn distance_km(&self, other: &City) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.lon - self.lon).to_radians();
+
+        let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        // `min(1.0)` 防止浮点误差导致 `asin` 的参数超出范围
+        2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+    }
+
+    /// 从当前城市出发前往 `other` 时的初始方位角，正北为 0°，顺时针方向，范围是 `[0, 360)`
+    pub fn bearing(&self, other: &City) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lon = (other.lon - self.lon).to_radians();
+
+        let y = d_lon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+        // `atan2` 的结果是 `(-180, 180]`，转换到 `[0, 360)`
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+}
+
+impl Display for City {
+    // f 是一个缓冲区（Buffer），这个函数把格式化好的内容写到这个缓冲区中。
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
+        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+        // `{:.5}` 这样的精度设置会用在经纬度上，没有设置的时候保留三位小数
+        let precision = f.precision().unwrap_or(3);
+
+        // `write!` 和 `format!` 这两个宏非常相似,
+        // 只不过 `write!` 宏是向指定的缓冲区（第一个参数）中写数据，而`format!`是返回一个格式化好的字符串。
+        write!(
+            f,
+            "{}: {:.*}°{} {:.*}°{}",
+            self.name,
+            precision,
+            self.lat.abs(),
+            lat_c,
+            precision,
+            self.lon.abs(),
+            lon_c
+        )
+    }
+}
+
+/// 解析城市或者坐标时可能出现的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCityError {
+    /// 缺少 `名字: ` 部分
+    MissingName(String),
+    /// 坐标不是两个部分，或者某个部分的格式不正确
+    InvalidCoordinate(String),
+    /// 纬度超出了 `[-90, 90]`，或者经度超出了 `[-180, 180]`
+    OutOfRange(String),
+}
+
+impl Display for ParseCityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCityError::MissingName(s) => write!(f, "missing city name in `{}`", s),
+            ParseCityError::InvalidCoordinate(s) => write!(f, "invalid coordinate `{}`", s),
+            ParseCityError::OutOfRange(s) => write!(f, "coordinate out of range `{}`", s),
+        }
+    }
+}
+
+impl Error for ParseCityError {}
+
+/// 格式是 `名字: 坐标`，也就是 `{}` 输出的内容，例如 `Dublin: 53.348°N 6.260°W`，
+/// 坐标部分支持的格式见 [`parse_coordinates`]。
+impl FromStr for City {
+    type Err = ParseCityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 名字里面可能有 `:`，所以从右边开始找
+        let (name, coordinates) = s
+            .rsplit_once(':')
+            .ok_or_else(|| ParseCityError::MissingName(s.to_string()))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ParseCityError::MissingName(s.to_string()));
+        }
+
+        let (lat, lon) = parse_coordinates(coordinates)?;
+        Ok(City::new(name, lat, lon))
+    }
+}
+
+/// 解析一对经纬度，返回 `(纬度, 经度)`
+///
+/// 两个部分之间用空白或者 `,` 分隔，每个部分可以是下面的任意一种格式：
+///
+/// - 十进制度数加上方向，例如 `53.348°N`
+/// - 度分秒加上方向，例如 `53°20'52"N`，也可以只写到分 `53°20.87'N`
+/// - 带符号的十进制度数，例如 `-6.259722`，这个时候第一个部分是纬度，第二个部分是经度
+///
+/// 两个部分都带有方向的时候顺序可以颠倒，例如 `6.260°W 53.348°N`。
+pub fn parse_coordinates(s: &str) -> Result<(f64, f64), ParseCityError> {
+    let invalid = || ParseCityError::InvalidCoordinate(s.trim().to_string());
+
+    let parts: Vec<&str> = if s.contains(',') {
+        s.split(',').map(str::trim).collect()
+    } else {
+        s.split_whitespace().collect()
+    };
+    let [first, second] = parts[..] else {
+        return Err(invalid());
+    };
+
+    let first = parse_angle(first).ok_or_else(invalid)?;
+    let second = parse_angle(second).ok_or_else(invalid)?;
+
+    let (lat, lon) = match (first.1, second.1) {
+        (Some(Axis::Lon), Some(Axis::Lat)) => (second.0, first.0),
+        (Some(Axis::Lon), _) | (_, Some(Axis::Lat)) => return Err(invalid()),
+        _ => (first.0, second.0),
+    };
+
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return Err(ParseCityError::OutOfRange(s.trim().to_string()));
+    }
+    Ok((lat, lon))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Lat,
+    Lon,
+}
+
+// 解析一个角度，返回带符号的度数，以及方向字母表示的是纬度还是经度
+fn parse_angle(s: &str) -> Option<(f64, Option<Axis>)> {
+    let (body, sign, axis) = match s.chars().last()? {
+        'N' | 'n' => (&s[..s.len() - 1], 1.0, Some(Axis::Lat)),
+        'S' | 's' => (&s[..s.len() - 1], -1.0, Some(Axis::Lat)),
+        'E' | 'e' => (&s[..s.len() - 1], 1.0, Some(Axis::Lon)),
+        'W' | 'w' => (&s[..s.len() - 1], -1.0, Some(Axis::Lon)),
+        _ => (s, 1.0, None),
+    };
+
+    let Some((degrees, rest)) = body.split_once('°') else {
+        // 没有 `°` 的时候只能是一个十进制数字，比如 `-6.26` 或者 `6.26W`
+        return Some((sign * parse_number(body, axis.is_some())?, axis));
+    };
+    let degrees = parse_number(degrees, axis.is_some())?;
+
+    // `°` 后面依次是可选的分（`'` 或者 `′`）和秒（`"` 或者 `″`）
+    let (minutes, rest) = match rest.split_once(['\'', '′']) {
+        Some((minutes, rest)) => (parse_number(minutes, true)?, rest),
+        None => (0.0, rest),
+    };
+    let (seconds, rest) = match rest.split_once(['"', '″']) {
+        Some((seconds, rest)) => (parse_number(seconds, true)?, rest),
+        None => (0.0, rest),
+    };
+    if !rest.is_empty() || minutes >= 60.0 || seconds >= 60.0 {
+        return None;
+    }
+    // 只有度数可以带小数，或者最后一个部分可以带小数，`53.5°30'` 这种写法是有歧义的
+    if (minutes != 0.0 || seconds != 0.0) && degrees.fract() != 0.0 {
+        return None;
+    }
+
+    let magnitude = degrees.abs() + minutes / 60.0 + seconds / 3600.0;
+    Some((sign * degrees.signum() * magnitude, axis))
+}
+
+// 解析一个十进制数字，`unsigned` 为 `true` 的时候不允许带符号，例如带有方向字母的时候
+fn parse_number(s: &str, unsigned: bool) -> Option<f64> {
+    let s = s.trim();
+    if s.is_empty() || (unsigned && s.starts_with(['+', '-'])) {
+        return None;
+    }
+    // `f64` 的解析会接受 `inf` `NaN` 这类内容，坐标里面不应该出现
+    if !s
+        .trim_start_matches(['+', '-'])
+        .starts_with(|c: char| c.is_ascii_digit())
+    {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    fn dublin() -> City {
+        City::new("Dublin", 53.347778, -6.259722)
+    }
+
+    #[test]
+    fn display_precision() {
+        assert_eq!(dublin().to_string(), "Dublin: 53.348°N 6.260°W");
+        assert_eq!(format!("{:.5}", dublin()), "Dublin: 53.34778°N 6.25972°W");
+        assert_eq!(format!("{:.0}", dublin()), "Dublin: 53°N 6°W");
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let city: City = format!("{:.6}", dublin()).parse().unwrap();
+        assert_eq!(city, dublin());
+
+        let city: City = dublin().to_string().parse().unwrap();
+        assert_eq!(city.name, "Dublin");
+        assert_close(city.lat, 53.348, 1e-9);
+        assert_close(city.lon, -6.260, 1e-9);
+    }
+
+    #[test]
+    fn parse_coordinate_forms() {
+        let (lat, lon) = parse_coordinates("53.347778, -6.259722").unwrap();
+        assert_eq!((lat, lon), (53.347778, -6.259722));
+
+        let (lat, lon) = parse_coordinates(r#"53°20'52"N 6°15'35"W"#).unwrap();
+        assert_close(lat, 53.347778, 1e-6);
+        assert_close(lon, -6.259722, 1e-6);
+
+        let (lat, lon) = parse_coordinates("33°52.2′S 151°12.5′E").unwrap();
+        assert_close(lat, -33.87, 1e-9);
+        assert_close(lon, 151.208333, 1e-6);
+
+        // 带有方向的时候顺序可以颠倒
+        assert_eq!(parse_coordinates("6.26°W 53.35°N"), Ok((53.35, -6.26)));
+        assert_eq!(parse_coordinates("0°N, 0°E"), Ok((0.0, 0.0)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        for text in [
+            "53.348°N",
+            "53.348°E 6.260°W",
+            "-53.348°N 6.260°W",
+            "53°60'N 6°W",
+            "53.5°30'N 6°W",
+            "NaN, 0",
+            "53.348°N 6.260°W 1",
+        ] {
+            assert_eq!(
+                parse_coordinates(text),
+                Err(ParseCityError::InvalidCoordinate(text.to_string())),
+                "{}",
+                text
+            );
+        }
+        assert_eq!(
+            parse_coordinates("91, 0"),
+            Err(ParseCityError::OutOfRange("91, 0".to_string()))
+        );
+        assert_eq!(
+            "53.348°N 6.260°W".parse::<City>(),
+            Err(ParseCityError::MissingName("53.348°N 6.260°W".to_string()))
+        );
+    }
+
+    #[test]
+    fn distance_and_bearing() {
+        let oslo = City::new("Oslo", 59.95, 10.75);
+        assert_close(dublin().distance_km(&oslo), 1266.96, 0.01);
+        assert_close(dublin().bearing(&oslo), 47.86, 0.01);
+        assert_eq!(dublin().distance_km(&dublin()), 0.0);
+
+        // 赤道上向东走四分之一圈
+        let origin = City::new("a", 0.0, 0.0);
+        let east = City::new("b", 0.0, 90.0);
+        assert_close(
+            origin.distance_km(&east),
+            EARTH_RADIUS_KM * std::f64::consts::FRAC_PI_2,
+            1e-6,
+        );
+        assert_close(origin.bearing(&east), 90.0, 1e-9);
+        assert_close(east.bearing(&origin), 270.0, 1e-9);
+    }
+}
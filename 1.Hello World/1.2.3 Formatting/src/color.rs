@@ -0,0 +1,497 @@
+use std::error::Error;
+use std::fmt::{self, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+// 实现 {} 特性 输出 RGB (x, x, x) 格式
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "RGB ({}, {}, {})", self.red, self.green, self.blue)
+    }
+}
+
+// 实现 {:x} 特性 输出 0xaabbcc 格式
+// 使用 {:#x} 的时候输出 CSS 使用的 #aabbcc 格式
+impl fmt::LowerHex for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // `#` 标记可以通过 `alternate` 方法获取
+        let prefix = if f.alternate() { "#" } else { "0x" };
+        // 使用 {:x} 格式把数字转成小写16进制文本
+        let lower_hex = |x: u8| format!("{:x}", x);
+        // 合并输出三个独立颜色单元的文字并自动左侧补齐空格
+        write!(
+            f,
+            "{}{:0>2}{:0>2}{:0>2}",
+            prefix,
+            lower_hex(self.red),
+            lower_hex(self.green),
+            lower_hex(self.blue),
+        )
+    }
+}
+
+// 实现 {:X} 特性 输出 0xAABBCC 格式
+// 使用 {:#X} 的时候输出 CSS 使用的 #AABBCC 格式
+impl fmt::UpperHex for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let prefix = if f.alternate() { "#" } else { "0x" };
+        // 使用 {:X} 格式把数字转成大写16进制文本
+        let upper_hex = |x: u8| format!("{:X}", x);
+        // 同上
+        write!(
+            f,
+            "{}{:0>2}{:0>2}{:0>2}",
+            prefix,
+            upper_hex(self.red),
+            upper_hex(self.green),
+            upper_hex(self.blue),
+        )
+    }
+}
+
+// `u32` 的低 24 位按照 0xRRGGBB 的顺序存放三个颜色，最高的 8 位会被忽略
+impl From<u32> for Color {
+    fn from(value: u32) -> Self {
+        Color {
+            red: (value >> 16) as u8,
+            green: (value >> 8) as u8,
+            blue: value as u8,
+        }
+    }
+}
+
+impl From<Color> for u32 {
+    fn from(color: Color) -> Self {
+        (color.red as u32) << 16 | (color.green as u32) << 8 | color.blue as u32
+    }
+}
+
+/// HSL 颜色空间，色相（hue）的范围是 `[0, 360)`，饱和度（saturation）和亮度（lightness）的范围是 `[0, 1]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let r = color.red as f64 / 255.0;
+        let g = color.green as f64 / 255.0;
+        let b = color.blue as f64 / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+
+        // 灰色没有色相和饱和度
+        if delta == 0.0 {
+            return Hsl {
+                hue: 0.0,
+                saturation: 0.0,
+                lightness,
+            };
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        Hsl {
+            hue,
+            saturation,
+            lightness,
+        }
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let hue = hsl.hue.rem_euclid(360.0);
+        let saturation = hsl.saturation.clamp(0.0, 1.0);
+        let lightness = hsl.lightness.clamp(0.0, 1.0);
+
+        // 色度（chroma），以及色相所在的 60° 区间中第二大的颜色分量
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+        let m = lightness - chroma / 2.0;
+
+        let (r, g, b) = match hue as u32 / 60 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+        Color {
+            red: channel(r),
+            green: channel(g),
+            blue: channel(b),
+        }
+    }
+}
+
+/// 解析颜色时可能出现的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// `#rgb` `#rrggbb` `0xrrggbb` 格式不正确
+    InvalidHex(String),
+    /// `rgb(r, g, b)` 格式不正确
+    InvalidRgb(String),
+    /// 不认识的颜色名字
+    UnknownName(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidHex(s) => write!(f, "invalid hex color `{}`", s),
+            ParseColorError::InvalidRgb(s) => write!(f, "invalid rgb color `{}`", s),
+            ParseColorError::UnknownName(s) => write!(f, "unknown color name `{}`", s),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// 支持的格式有 `#rrggbb` `#rgb` `0xrrggbb` `rgb(r, g, b)` `RGB (r, g, b)` 以及 CSS 中的颜色名字，
+/// 也就是说 `{}` `{:x}` `{:X}` `{:#x}` `{:#X}` 输出的内容都可以再解析回来。
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(hex) = s.strip_prefix('#') {
+            // `#rgb` 是 `#rrggbb` 的简写，每一位重复一次，例如 `#f80` 就是 `#ff8800`
+            let hex = match hex.len() {
+                3 => hex.chars().flat_map(|c| [c, c]).collect(),
+                _ => hex.to_string(),
+            };
+            return parse_hex(&hex).ok_or_else(|| ParseColorError::InvalidHex(s.to_string()));
+        }
+
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            return parse_hex(hex).ok_or_else(|| ParseColorError::InvalidHex(s.to_string()));
+        }
+
+        if s.get(..3)
+            .is_some_and(|rgb| rgb.eq_ignore_ascii_case("rgb"))
+        {
+            return parse_rgb(&s[3..]).ok_or_else(|| ParseColorError::InvalidRgb(s.to_string()));
+        }
+
+        let name = s.to_ascii_lowercase();
+        NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |&(name, _)| name)
+            .map(|index| Color::from(NAMED_COLORS[index].1))
+            .map_err(|_| ParseColorError::UnknownName(s.to_string()))
+    }
+}
+
+// 解析六位十六进制数字
+fn parse_hex(hex: &str) -> Option<Color> {
+    // `from_str_radix` 会接受 `+` 号，所以需要先检查每一位都是十六进制数字
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(Color::from)
+}
+
+// 解析 `(r, g, b)` 部分，括号前面可以有空格
+fn parse_rgb(s: &str) -> Option<Color> {
+    let inner = s.trim_start().strip_prefix('(')?.strip_suffix(')')?;
+    let mut parts = inner.split(',').map(|part| part.trim().parse::<u8>());
+
+    let color = Color {
+        red: parts.next()?.ok()?,
+        green: parts.next()?.ok()?,
+        blue: parts.next()?.ok()?,
+    };
+
+    // 多余的部分也是错误
+    match parts.next() {
+        Some(_) => None,
+        None => Some(color),
+    }
+}
+
+/// CSS 中定义的所有颜色名字，按照名字排序，方便二分查找
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [Color; 4] = [
+        Color {
+            red: 128,
+            green: 255,
+            blue: 90,
+        },
+        Color {
+            red: 0,
+            green: 3,
+            blue: 254,
+        },
+        Color {
+            red: 0,
+            green: 0,
+            blue: 0,
+        },
+        Color {
+            red: 255,
+            green: 255,
+            blue: 255,
+        },
+    ];
+
+    #[test]
+    fn formats_round_trip() {
+        for color in SAMPLES {
+            for text in [
+                format!("{}", color),
+                format!("{:x}", color),
+                format!("{:X}", color),
+                format!("{:#x}", color),
+                format!("{:#X}", color),
+            ] {
+                assert_eq!(text.parse::<Color>(), Ok(color), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn alternate_hex_is_css() {
+        let color = Color::from(0x80ff5a);
+        assert_eq!(format!("{:x}", color), "0x80ff5a");
+        assert_eq!(format!("{:#x}", color), "#80ff5a");
+        assert_eq!(format!("{:#X}", color), "#80FF5A");
+    }
+
+    #[test]
+    fn parse_css_forms() {
+        assert_eq!("#f80".parse(), Ok(Color::from(0xff8800)));
+        assert_eq!("rgb(1, 2,3)".parse(), Ok(Color::from(0x010203)));
+        assert_eq!(" RebeccaPurple ".parse(), Ok(Color::from(0x663399)));
+        assert_eq!(
+            "#12345".parse::<Color>(),
+            Err(ParseColorError::InvalidHex("#12345".to_string()))
+        );
+        assert_eq!(
+            "0x+12345".parse::<Color>(),
+            Err(ParseColorError::InvalidHex("0x+12345".to_string()))
+        );
+        assert_eq!(
+            "rgb(1, 2, 256)".parse::<Color>(),
+            Err(ParseColorError::InvalidRgb("rgb(1, 2, 256)".to_string()))
+        );
+        assert_eq!(
+            "rgb(1, 2, 3, 4)".parse::<Color>(),
+            Err(ParseColorError::InvalidRgb("rgb(1, 2, 3, 4)".to_string()))
+        );
+        assert_eq!(
+            "blurple".parse::<Color>(),
+            Err(ParseColorError::UnknownName("blurple".to_string()))
+        );
+    }
+
+    #[test]
+    fn named_colors_are_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(NAMED_COLORS.len(), 148);
+    }
+
+    #[test]
+    fn u32_round_trip() {
+        for color in SAMPLES {
+            assert_eq!(Color::from(u32::from(color)), color);
+        }
+        assert_eq!(u32::from(Color::from(0xff12_3456)), 0x12_3456);
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        for red in (0..=255).step_by(3) {
+            for green in (0..=255).step_by(5) {
+                for blue in (0..=255).step_by(7) {
+                    let color = Color { red, green, blue };
+                    assert_eq!(Color::from(Hsl::from(color)), color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hsl_values() {
+        let hsl = Hsl::from(Color::from(0x00ff00));
+        assert_eq!((hsl.hue, hsl.saturation, hsl.lightness), (120.0, 1.0, 0.5));
+
+        let gray = Hsl::from(Color::from(0x808080));
+        assert_eq!((gray.hue, gray.saturation), (0.0, 0.0));
+    }
+}
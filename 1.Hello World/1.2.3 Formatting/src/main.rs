@@ -1,99 +1,47 @@
-use std::fmt::{self, Display, Formatter};
+// 城市和颜色相关的格式化和解析分别放在了单独的 `city.rs` 和 `color.rs` 文件中
+mod city;
+mod color;
 
-struct City {
-    name: &'static str,
-    // 纬度
-    lat: f32,
-    // 经度
-    lon: f32,
-}
-
-impl Display for City {
-    // f 是一个缓冲区（Buffer），这个函数把格式化好的内容写到这个缓冲区中。
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
-        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+use city::City;
+use color::{Color, Hsl};
 
-        // `write!` 和 `format!` 这两个宏非常相似,
-        // 只不过 `write!` 宏是向指定的缓冲区（第一个参数）中写数据，而`format!`是返回一个格式化好的字符串。
-        write!(
-            f,
-            "{}: {:.3}°{} {:.3}°{}",
-            self.name,
-            self.lat.abs(),
-            lat_c,
-            self.lon.abs(),
-            lon_c
-        )
+fn main() {
+    let cities = [
+        City::new("Dublin", 53.347778, -6.259722),
+        City::new("Oslo", 59.95, 10.75),
+        City::new("Vancouver", 49.25, -123.1),
+    ];
+    for city in &cities {
+        println!("{}", city);
     }
-}
-
-#[derive(Debug)]
-struct Color {
-    red: u8,
-    green: u8,
-    blue: u8,
-}
 
-// 实现 {} 特性 输出 RGB (x, x, x) 格式
-impl fmt::Display for Color {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "RGB ({}, {}, {})", self.red, self.green, self.blue)
-    }
-}
+    // 精度设置会用在经纬度上
+    println!("{:.5}", cities[0]);
 
-// 实现 {:x} 特性 输出 0xaabbcc 格式
-impl fmt::LowerHex for Color {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        // 使用 {:x} 格式把数字转成小写16进制文本
-        let lower_hex = |x: u8| format!("{:x}", x);
-        // 合并输出三个独立颜色单元的文字并自动左侧补齐空格
-        write!(
-            f,
-            "0x{:0>2}{:0>2}{:0>2}",
-            lower_hex(self.red),
-            lower_hex(self.green),
-            lower_hex(self.blue),
-        )
+    // 输出的内容可以再解析回来，还支持带符号的十进制和度分秒的写法
+    for text in [
+        "Dublin: 53.348°N 6.260°W",
+        "Oslo: 59.95, 10.75",
+        r#"Vancouver: 49°15'N 123°06'W"#,
+        "Nowhere: 91°N 0°E",
+    ] {
+        match text.parse::<City>() {
+            Ok(city) => println!("{:>28} => {:.4}", text, city),
+            Err(e) => println!("{:>28} => error: {}", text, e),
+        }
     }
-}
 
-// 实现 {:X} 特性 输出 0xAABBCC 格式
-impl fmt::UpperHex for Color {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        // 使用 {:X} 格式把数字转成大写16进制文本
-        let upper_hex = |x: u8| format!("{:X}", x);
-        // 同上
-        write!(
-            f,
-            "0x{:0>2}{:0>2}{:0>2}",
-            upper_hex(self.red),
-            upper_hex(self.green),
-            upper_hex(self.blue),
-        )
+    // 两个城市之间的距离和初始方位角
+    for pair in cities.windows(2) {
+        println!(
+            "{} -> {}: {:.1} km, bearing {:.1}°",
+            pair[0].name,
+            pair[1].name,
+            pair[0].distance_km(&pair[1]),
+            pair[0].bearing(&pair[1])
+        );
     }
-}
 
-fn main() {
-    for city in [
-        City {
-            name: "Dublin",
-            lat: 53.347778,
-            lon: -6.259722,
-        },
-        City {
-            name: "Oslo",
-            lat: 59.95,
-            lon: 10.75,
-        },
-        City {
-            name: "Vancouver",
-            lat: 49.25,
-            lon: -123.1,
-        },
-    ] {
-        println!("{}", city);
-    }
     for color in [
         Color {
             red: 128,
@@ -115,4 +63,35 @@ fn main() {
         // 字符串，大写十六进制，小写十六进制
         println!("{0:?} {0:x} {0:X}", color);
     }
+
+    // `{:#x}` `{:#X}` 输出 CSS 使用的格式
+    let color = Color::from(0x80ff5a);
+    println!("{} {:#x} {:#X}", color, color, color);
+
+    // 所有输出的格式都可以再解析回来，还支持 `#rgb` 简写和 CSS 的颜色名字
+    for text in [
+        "RGB (0, 3, 254)",
+        "0x80ff5a",
+        "#80FF5A",
+        "#f80",
+        "rgb(255, 99, 71)",
+        "RebeccaPurple",
+        "#zzz",
+    ] {
+        match text.parse::<Color>() {
+            Ok(color) => println!("{:>16} => {:#x}", text, color),
+            Err(e) => println!("{:>16} => error: {}", text, e),
+        }
+    }
+
+    // `u32` 和 HSL 颜色空间的互相转换
+    let hsl = Hsl::from(Color::from(0x663399));
+    println!(
+        "hsl({:.0}, {:.0}%, {:.0}%) => {:#x}",
+        hsl.hue,
+        hsl.saturation * 100.0,
+        hsl.lightness * 100.0,
+        Color::from(hsl)
+    );
+    println!("{:#x} as u32 is {}", color, u32::from(color));
 }
@@ -7,6 +7,9 @@
 //! 模块中可以放置 函数(function)、结构体(struct)、特性(trait)、和实现(impl)、甚至其他的模块(module)
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::needless_pub_self)]
+
 /// 默认情况下模块内部的所有内容都是私有(private)的，
 /// 需要通过 `pub` 关键字来让私有变成外部可访问的。
 // 模块名为 my_mod 的模块
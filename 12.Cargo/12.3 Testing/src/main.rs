@@ -29,7 +29,7 @@ fn main() {
 }
 
 /// 可能会出现竞争关系的测试代码。
-/// 这个测试运行完成以后，文件的内容预期是
+/// 这个测试运行完成以后，临时目录中 `ferris-<进程号>.txt` 文件的内容预期是
 /// ```text
 /// Ferris
 /// Ferris
@@ -61,6 +61,14 @@ mod tests {
     // 导入使用的模块
     use std::fs::OpenOptions;
     use std::io::Write;
+    use std::path::PathBuf;
+
+    // 两个测试写同一个文件，放在临时目录中，运行测试的时候不会修改工程目录中的文件。
+    // 文件名中带有进程号，每次运行测试都写一个新的文件，不会接着上一次运行的内容继续写，
+    // 同时运行的其他测试进程也不会写到这个文件中
+    fn ferris_txt() -> PathBuf {
+        std::env::temp_dir().join(format!("ferris-{}.txt", std::process::id()))
+    }
 
     // 写文件
     #[test]
@@ -69,7 +77,7 @@ mod tests {
         let mut file = OpenOptions::new()
             .append(true)
             .create(true)
-            .open("ferris.txt")
+            .open(ferris_txt())
             .expect("Failed to open ferris.txt");
 
         // 向文件中写入5次 `Ferris`
@@ -86,7 +94,7 @@ mod tests {
         let mut file = OpenOptions::new()
             .append(true)
             .create(true)
-            .open("ferris.txt")
+            .open(ferris_txt())
             .expect("Failed to open ferris.txt");
 
         // 向文件中写入5次 `Corro`
@@ -5,6 +5,10 @@
 //! 在真实的工程中，你应该解决死代码的问题，而不是使用属性来禁用警告！！！
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`，
+// 删除这一行可以看到下面 `noisy_unused_function` 的警告
+#![allow(dead_code)]
+
 fn used_function() {}
 
 // `#[allow(dead_code)]` 属性可以禁用掉 `dead_code` 的代码检查
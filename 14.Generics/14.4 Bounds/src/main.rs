@@ -5,6 +5,9 @@
 //! 所有就有了泛型的约束，要求传递的参数必须实现某些特定的特性。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(dead_code)]
+
 fn example01() {
     use std::fmt::Display;
 
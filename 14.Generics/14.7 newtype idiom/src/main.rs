@@ -5,6 +5,9 @@
 //! 这样的好处是可以让 `api` 更加清晰，不会造成歧义。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(unused_variables)]
+
 struct Years(i64);
 
 struct Days(i64);
@@ -116,7 +116,11 @@ fn associated_types() {
     println!("The difference is: {}", difference(&container));
 }
 
+rbe::sections! {
+    the_problem: "没有关联类型时的问题",
+    associated_types: "关联类型",
+}
+
 fn main() {
-    the_problem();
-    associated_types();
+    rbe::run(SECTIONS);
 }
@@ -0,0 +1,343 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// 长度单位，幽灵类型 `Unit` 需要实现这个特性
+pub trait Unit {
+    /// 输出的时候使用的符号，例如 `in`
+    const SYMBOL: &'static str;
+    /// 一个单位等于多少微米，所有单位的定义都是微米的整数倍，
+    /// 使用整数保存是因为像 25.4 这样的小数不能用浮点数精确表示
+    const SCALE: u64;
+}
+
+// 和之前一样使用没有枚举项的枚举作为单位，单位只是一个标记，不需要实现其他的特性
+#[derive(Debug)]
+pub enum Inch {}
+#[derive(Debug)]
+pub enum Foot {}
+#[derive(Debug)]
+pub enum Mm {}
+#[derive(Debug)]
+pub enum Meter {}
+
+impl Unit for Inch {
+    const SYMBOL: &'static str = "in";
+    const SCALE: u64 = 25_400;
+}
+
+impl Unit for Foot {
+    const SYMBOL: &'static str = "ft";
+    const SCALE: u64 = 304_800;
+}
+
+impl Unit for Mm {
+    const SYMBOL: &'static str = "mm";
+    const SCALE: u64 = 1_000;
+}
+
+impl Unit for Meter {
+    const SYMBOL: &'static str = "m";
+    const SCALE: u64 = 1_000_000;
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// 从单位 `Src` 换算到单位 `Dst` 的比例，约分以后的分子和分母，`power` 是 2 的时候用于面积
+//
+// 约分以后再使用 `f64` 计算乘方，结果小于 2^53 的时候是精确的整数，
+// 更大的比例会有舍入，但是不会像整数的 `pow` 那样溢出
+fn ratio<Src: Unit, Dst: Unit>(power: i32) -> (f64, f64) {
+    let divisor = gcd(Src::SCALE, Dst::SCALE);
+    let numerator = (Src::SCALE / divisor) as f64;
+    let denominator = (Dst::SCALE / divisor) as f64;
+    (numerator.powi(power), denominator.powi(power))
+}
+
+// 先乘以分子再除以分母，分子和分母都是精确的整数，
+// 所以结果和精确值相比最多只有一次舍入，例如 12 in 正好是 304.8 mm
+fn rescale<Src: Unit, Dst: Unit>(value: f64, power: i32) -> f64 {
+    let (numerator, denominator) = ratio::<Src, Dst>(power);
+    value * numerator / denominator
+}
+
+/// `Length` 是一个拥有幽灵泛型 `Unit` 的一个元组结构，
+/// 我们明确指定了元组拥有一个 `f64` 的实际类型，
+/// f64 天然实现了 `Clone` 和 `Copy` 特性。
+pub struct Length<U>(pub f64, pub PhantomData<U>);
+
+impl<U: Unit> Length<U> {
+    pub fn new(value: f64) -> Length<U> {
+        Length(value, PhantomData)
+    }
+
+    /// 明确的转换成其他的单位，例如 `one_foot.convert::<Mm>()`
+    pub fn convert<To: Unit>(self) -> Length<To> {
+        Length::new(rescale::<U, To>(self.0, 1))
+    }
+}
+
+/// 面积，由两个单位相同的长度相乘得到，单位是 `Unit` 的平方
+pub struct Area<U>(pub f64, pub PhantomData<U>);
+
+// `derive` 会要求泛型参数 `U` 也实现对应的特性，例如 `#[derive(Clone)]` 生成的是
+// `impl<U: Clone> Clone for Length<U>`，但是单位只是一个标记，所以手动实现这些特性，
+// 只比较和复制里面的 `f64`
+macro_rules! impl_value_traits {
+    ($($name:ident),*) => {
+        $(
+            impl<U> Clone for $name<U> {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl<U> Copy for $name<U> {}
+
+            impl<U> PartialEq for $name<U> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl<U> PartialOrd for $name<U> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            impl<U> fmt::Debug for $name<U> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!($name))
+                        .field(&self.0)
+                        .field(&self.1)
+                        .finish()
+                }
+            }
+        )*
+    };
+}
+
+impl_value_traits!(Length, Area);
+
+impl<U: Unit> Area<U> {
+    pub fn new(value: f64) -> Area<U> {
+        Area(value, PhantomData)
+    }
+
+    /// 面积的换算比例是长度的平方
+    pub fn convert<To: Unit>(self) -> Area<To> {
+        Area::new(rescale::<U, To>(self.0, 2))
+    }
+}
+
+// 数字和单位之间有一个空格，例如 `24 in`
+impl<U: Unit> fmt::Display for Length<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        write!(f, " {}", U::SYMBOL)
+    }
+}
+
+impl<U: Unit> fmt::Display for Area<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        write!(f, " {}²", U::SYMBOL)
+    }
+}
+
+/// `Add` 特性会重定义 `Length<Unit>` 这个类型的 `+` 操作符。
+impl<U> Add for Length<U> {
+    type Output = Length<U>;
+
+    // add() 返回一个新的 `Length` 结构保存着加法的结果。
+    fn add(self, rhs: Length<U>) -> Length<U> {
+        // 实现 `+` 号的操作。
+        Length(self.0 + rhs.0, PhantomData)
+    }
+}
+
+impl<U> Sub for Length<U> {
+    type Output = Length<U>;
+
+    fn sub(self, rhs: Length<U>) -> Length<U> {
+        Length(self.0 - rhs.0, PhantomData)
+    }
+}
+
+// 长度乘以或者除以一个没有单位的数字，结果还是长度
+impl<U> Mul<f64> for Length<U> {
+    type Output = Length<U>;
+
+    fn mul(self, rhs: f64) -> Length<U> {
+        Length(self.0 * rhs, PhantomData)
+    }
+}
+
+impl<U> Div<f64> for Length<U> {
+    type Output = Length<U>;
+
+    fn div(self, rhs: f64) -> Length<U> {
+        Length(self.0 / rhs, PhantomData)
+    }
+}
+
+// 两个单位相同的长度相乘得到面积，单位不同的时候需要先转换
+impl<U> Mul for Length<U> {
+    type Output = Area<U>;
+
+    fn mul(self, rhs: Length<U>) -> Area<U> {
+        Area(self.0 * rhs.0, PhantomData)
+    }
+}
+
+impl<U> Add for Area<U> {
+    type Output = Area<U>;
+
+    fn add(self, rhs: Area<U>) -> Area<U> {
+        Area(self.0 + rhs.0, PhantomData)
+    }
+}
+
+impl<U> Sub for Area<U> {
+    type Output = Area<U>;
+
+    fn sub(self, rhs: Area<U>) -> Area<U> {
+        Area(self.0 - rhs.0, PhantomData)
+    }
+}
+
+impl<U> Mul<f64> for Area<U> {
+    type Output = Area<U>;
+
+    fn mul(self, rhs: f64) -> Area<U> {
+        Area(self.0 * rhs, PhantomData)
+    }
+}
+
+// 面积除以一条边的长度得到另一条边的长度
+impl<U> Div<Length<U>> for Area<U> {
+    type Output = Length<U>;
+
+    fn div(self, rhs: Length<U>) -> Length<U> {
+        Length(self.0 / rhs.0, PhantomData)
+    }
+}
+
+// 可以直接对所有房间的面积求和
+impl<U> Sum for Area<U> {
+    fn sum<I: Iterator<Item = Area<U>>>(iter: I) -> Area<U> {
+        Area(iter.map(|area| area.0).sum(), PhantomData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Length::<Inch>::new(12.0);
+        let b = Length::<Inch>::new(5.0);
+        assert_eq!(a + b, Length::new(17.0));
+        assert_eq!(a - b, Length::new(7.0));
+        assert_eq!(a * 2.0, Length::new(24.0));
+        assert_eq!(a / 4.0, Length::new(3.0));
+        assert!(a > b);
+        assert!(Length::<Mm>::new(1.0) < Length::new(2.0));
+    }
+
+    #[test]
+    fn unit_needs_no_derives() {
+        // 没有实现任何特性的单位也可以复制、比较和输出 `Length`
+        enum Furlong {}
+        impl Unit for Furlong {
+            const SYMBOL: &'static str = "fur";
+            const SCALE: u64 = 201_168_000;
+        }
+
+        let a = Length::<Furlong>::new(1.0);
+        let b = a;
+        assert_eq!(a, b);
+        assert!(a * 2.0 > b);
+        assert!(format!("{:?}", a).starts_with("Length(1.0, PhantomData<"));
+
+        // 比例很大的时候，面积换算中的乘方也不会溢出
+        enum AstronomicalUnit {}
+        impl Unit for AstronomicalUnit {
+            const SYMBOL: &'static str = "au";
+            const SCALE: u64 = 149_597_870_700_000_000;
+        }
+        let area = Area::<AstronomicalUnit>::new(1.0).convert::<Meter>();
+        assert_eq!(area, Area::new(149_597_870_700.0_f64.powi(2)));
+        let back = Area::<Mm>::new(1.0).convert::<AstronomicalUnit>();
+        assert!(back.0 > 0.0 && back.0 < 1e-28);
+
+        assert_eq!(a.convert::<Meter>(), Length::new(201.168));
+        assert_eq!(
+            (a * a).convert::<Mm>() / Length::new(1.0),
+            Length::new(40_468_564_224.0)
+        );
+    }
+
+    #[test]
+    fn exact_conversions() {
+        let one_foot = Length::<Foot>::new(1.0);
+        assert_eq!(one_foot.convert::<Inch>(), Length::new(12.0));
+        assert_eq!(one_foot.convert::<Mm>(), Length::new(304.8));
+        assert_eq!(
+            Length::<Inch>::new(12.0).convert::<Mm>(),
+            Length::new(304.8)
+        );
+        assert_eq!(Length::<Inch>::new(1.0).convert::<Mm>(), Length::new(25.4));
+        assert_eq!(
+            Length::<Inch>::new(36.0).convert::<Foot>(),
+            Length::new(3.0)
+        );
+        assert_eq!(
+            Length::<Mm>::new(254.0).convert::<Inch>(),
+            Length::new(10.0)
+        );
+        assert_eq!(
+            Length::<Meter>::new(1.5).convert::<Mm>(),
+            Length::new(1500.0)
+        );
+        // 转换过去再转换回来得到原来的值
+        let length = Length::<Inch>::new(7.0);
+        assert_eq!(length.convert::<Mm>().convert::<Inch>(), length);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Length::<Inch>::new(24.0).to_string(), "24 in");
+        assert_eq!(Length::<Mm>::new(25.4).to_string(), "25.4 mm");
+        assert_eq!(format!("{:.1}", Length::<Meter>::new(2.0)), "2.0 m");
+        assert_eq!(Area::<Foot>::new(120.0).to_string(), "120 ft²");
+    }
+
+    #[test]
+    fn areas() {
+        let width = Length::<Foot>::new(12.0);
+        let depth = Length::<Foot>::new(10.0);
+        let room = width * depth;
+        assert_eq!(room, Area::new(120.0));
+        assert_eq!(room / width, depth);
+        assert_eq!(room.convert::<Inch>(), Area::new(17280.0));
+        assert_eq!(
+            Area::<Meter>::new(2.0).convert::<Mm>(),
+            Area::new(2_000_000.0)
+        );
+
+        let total: Area<Foot> = [room, Area::new(30.0)].into_iter().sum();
+        assert_eq!(total, Area::new(150.0));
+        assert_eq!(total - room * 2.0, Area::new(-90.0));
+    }
+}
@@ -16,6 +16,9 @@
 
 use std::marker::PhantomData; // 导入幽灵类型
 
+// 带有单位的长度和面积放在了单独的 `length.rs` 文件中
+mod length;
+
 // 声明一个拥有幽灵类型的元组结构，两个泛型 `<A, B>` 泛型 `A` 拥有是常规的类型，泛型 `B` 是一个隐藏的幽灵类型。
 #[derive(PartialEq)] // 允许结构直接行进 `==` 运算
 struct PhantomTuple<A, B>(A, PhantomData<B>);
@@ -32,31 +35,8 @@ struct PhantomStruct<A, B> {
 
 // 幽灵小测试
 fn testcase_unit_clarification() {
-    // use std::marker::PhantomData;
-    use std::ops::Add;
-
-    /// 创建两个枚举类型
-    #[derive(Debug, Clone, Copy)]
-    enum Inch {}
-    #[derive(Debug, Clone, Copy)]
-    enum Mm {}
-
-    /// `Length` 是一个拥有幽灵泛型 `Unit` 的一个元组结构，
-    /// 我们明确指定了元组拥有一个 `f64` 的实际类型，
-    /// f64 天然实现了 `Clone` 和 `Copy` 特性。
-    #[derive(Debug, Clone, Copy)]
-    struct Length<Unit>(f64, PhantomData<Unit>);
-
-    /// `Add` 特性会重定义 `Length<Unit>` 这个类型的 `+` 操作符。
-    impl<Unit> Add for Length<Unit> {
-        type Output = Length<Unit>;
-
-        // add() 返回一个新的 `Length` 结构保存着加法的结果。
-        fn add(self, rhs: Length<Unit>) -> Length<Unit> {
-            // 实现 `+` 号的操作。
-            Length(self.0 + rhs.0, PhantomData)
-        }
-    }
+    // `Inch`、`Mm` 两个单位和 `Length<Unit>` 的定义，以及 `+` 号等运算符的实现都在 `length.rs` 中
+    use length::{Area, Foot, Inch, Length, Mm};
 
     // 明确指明幽灵类型的实际类型是 `Inch`。
     let one_foot: Length<Inch> = Length(12.0, PhantomData);
@@ -76,6 +56,35 @@ fn testcase_unit_clarification() {
     // 没有意义的对比。
     // 编译错误: 类型不匹配
     // let one_feter = one_foot + one_meter;
+
+    // 不同的单位需要明确的转换以后才能计算，`Display` 会输出单位
+    println!("one foot = {}", one_foot.convert::<Mm>());
+    println!("one meter = {:.2}", one_meter.convert::<Inch>());
+    println!(
+        "one foot + one meter = {}",
+        one_foot.convert::<Mm>() + one_meter
+    );
+    println!(
+        "two feet > one meter: {}",
+        two_feet.convert::<Mm>() > one_meter
+    );
+    println!("one foot - 3 in = {}", one_foot - Length::new(3.0));
+    println!("one foot / 4 = {}", one_foot / 4.0);
+
+    // 长度相乘得到面积，可以用来计算房屋的面积
+    let rooms: [(&str, Length<Foot>, Length<Foot>); 3] = [
+        ("living room", Length::new(20.0), Length::new(15.0)),
+        ("bedroom", Length::new(12.0), Length::new(11.0)),
+        ("kitchen", Length::new(10.0), Length::new(8.5)),
+    ];
+    for (name, width, depth) in rooms {
+        println!("{:>11}: {} x {} = {}", name, width, depth, width * depth);
+    }
+    let total: Area<Foot> = rooms.iter().map(|&(_, width, depth)| width * depth).sum();
+    println!("total: {} = {:.2}", total, total.convert::<length::Meter>());
+
+    // 面积和长度不能相加
+    // let wrong = total + one_foot.convert::<Foot>();
 }
 
 fn main() {
@@ -6,6 +6,9 @@
 //! 什么时候资源应该被释放，或者哪些变量应该被销毁。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(unused_variables)]
+
 /// 在 `Rust` 中变量并不仅仅在栈中保存数据，还会在堆中申请内存并保存堆指针
 /// 比如说 `Box<T>`，`Rust` 中引用并加强了 `RAII` (Resource Acquisition Is Initialization 这个概念起源于 C++) 的使用，
 /// 所以每当一个对象的作用域消失了，则这个对象就会被销毁并且回收对应的资源。
@@ -58,7 +61,11 @@ fn descructor() {
     println!("Made a ToDrop!");
 }
 
+rbe::sections! {
+    raii: "资源获取即初始化",
+    descructor: "析构函数",
+}
+
 fn main() {
-    raii();
-    descructor();
+    rbe::run(SECTIONS);
 }
@@ -103,8 +103,12 @@ fn partial_moves() {
     println!("The person's age from person struct is {}", person.age);
 }
 
+rbe::sections! {
+    ownership_and_moves: "所有权和移动",
+    mutability: "所有权转移时的可变性",
+    partial_moves: "部分移动",
+}
+
 fn main() {
-    ownership_and_moves();
-    mutability();
-    partial_moves();
+    rbe::run(SECTIONS);
 }
@@ -0,0 +1,6 @@
+use rbe::snapshot::Snapshot;
+
+#[test]
+fn snapshot() {
+    Snapshot::sections(env!("CARGO_BIN_EXE_ownership_and_moves")).check();
+}
@@ -6,6 +6,9 @@
 //! 通过在变量前加上 `&` 关键字来创建一个引用。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::toplevel_ref_arg)]
+
 fn example01() {
     // 这个函数会转移传递进来的参数的所有权，函数执行完成以后就会销毁参数。
     fn eat_box_i32(boxed_i32: Box<i32>) {
@@ -226,13 +229,13 @@ fn the_ref_pattern() {
     println!("tuple is {:?}", mutable_tuple);
 }
 
+rbe::sections! {
+    example01: "基础示例",
+    mutability: "可变引用和不可变引用",
+    aliasing: "可变引用和不可变引用的借用关系",
+    the_ref_pattern: "`ref` 的使用场景",
+}
+
 fn main() {
-    // 基础示例
-    example01();
-    // 可变引用和不可变引用
-    mutability();
-    // 可变引用和不可变引用的借用关系
-    aliasing();
-    // `ref` 的使用场景
-    the_ref_pattern();
+    rbe::run(SECTIONS);
 }
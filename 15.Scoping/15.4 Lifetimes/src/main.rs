@@ -6,6 +6,10 @@
 //!
 //! 声明周期取决于当前变量声明的作用域，当变量发生转移则作用域也会转移。
 //!
+
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(dead_code, clippy::extra_unused_lifetimes, clippy::needless_lifetimes)]
+
 extern crate rand;
 
 /// 下面每个变量的线条指示了该变量的生命周期
@@ -360,25 +364,19 @@ fn elision() {
     println!("`annotated_pass`: {}", annotated_pass(&x));
 }
 
+rbe::sections! {
+    example01: "生命周期的基础展示",
+    explicit: "明确指定声明周期",
+    functions: "函数中的生命周期关系",
+    methods: "方法中的生命周期关系",
+    structs: "结构体中的生命周期关系",
+    traits: "特性中的生命周期",
+    bounds: "泛型约束中的生命周期",
+    coercion: "声明周期的强制转换",
+    statics: "字面量和 `'static`",
+    elision: "生命周期的省略规则",
+}
+
 fn main() {
-    // 生命周期的基础展示
-    example01();
-    // 明确指定声明周期
-    explicit();
-    // 函数中的生命周期关系
-    functions();
-    // 方法中的生命周期关系
-    methods();
-    // 结构体中的生命周期关系
-    structs();
-    // 特性中的生命周期
-    traits();
-    // 泛型约束中的生命周期
-    bounds();
-    // 声明周期的强制转换
-    coercion();
-    // 字面量和 `'static`
-    statics();
-
-    println!("Hello, world!");
+    rbe::run(SECTIONS);
 }
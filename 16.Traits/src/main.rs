@@ -7,6 +7,17 @@
 //!
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(
+    dead_code,
+    unused_imports,
+    clippy::assign_op_pattern,
+    clippy::let_and_return,
+    clippy::ptr_arg,
+    clippy::redundant_field_names,
+    clippy::useless_conversion
+)]
+
 use std::{
     f64::consts::E,
     fmt::{Debug, Display},
@@ -633,23 +644,19 @@ fn disambiguating_overlapping_traits() {
     AgeWidget::get(&form);
 }
 
+rbe::sections! {
+    example01: "特性基础展示",
+    derive: "属性宏快捷实现通用特性",
+    returning_traits_with_dyn: "使用 `dyn` 返回特性对象",
+    operator_overloading: "使用特性重载操作符",
+    drop_: "析构函数",
+    iterators: "迭代器",
+    impl_trait: "函数参数和返回值的特性约束",
+    clone_: "数据的复制",
+    supertraits: "特性的继承约束",
+    disambiguating_overlapping_traits: "特性方法名冲突的解决方案",
+}
+
 fn main() {
-    // 特性基础展示
-    example01();
-    // 属性宏快捷实现通用特性
-    derive();
-    // 使用特性重载操作符
-    operator_overloading();
-    // 析构函数
-    drop_();
-    // 迭代器
-    iterators();
-    // 函数参数和返回值的特性约束
-    impl_trait();
-    // 数据的复制
-    clone_();
-    // 特性的继承约束
-    supertraits();
-    // 特性方法名冲突的解决方案
-    disambiguating_overlapping_traits();
+    rbe::run(SECTIONS);
 }
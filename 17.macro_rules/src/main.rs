@@ -6,6 +6,9 @@
 //! 这样能避免因为优先级出现的问题。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::eq_op, clippy::erasing_op, clippy::ptr_arg)]
+
 ///
 /// 这是一个最基础的宏示例
 ///
@@ -261,19 +264,16 @@ fn variadic_interfaces() {
     }
 }
 
+rbe::sections! {
+    example01: "宏的基本使用",
+    designators: "宏的参数",
+    overload: "宏的多态",
+    repeat: "可变参数宏",
+    dont_repeat_yourself: "定义宏函数模版",
+    domain_specific_languages: "自定义语法 DSL",
+    variadic_interfaces: "可变多态宏",
+}
+
 fn main() {
-    // 宏的基本使用
-    example01();
-    // 宏的参数
-    designators();
-    // 宏的多态
-    overload();
-    // 可变参数宏
-    repeat();
-    // 定义宏函数模版
-    dont_repeat_yourself();
-    // 自定义语法 DSL
-    domain_specific_languages();
-    // 可变多态宏
-    variadic_interfaces();
+    rbe::run(SECTIONS);
 }
@@ -22,6 +22,16 @@
 //! 其他的错误处理情况可以参考[官方推荐的书中的错误处理方式](https://doc.rust-lang.org/book/ch09-00-error-handling.html)
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(
+    dead_code,
+    unused_variables,
+    clippy::manual_map,
+    clippy::map_flatten,
+    clippy::question_mark,
+    clippy::redundant_closure
+)]
+
 /// 最简单的处理错误方法就是使用 `panic!`，它可以明确的给出错误信息和崩溃前的调用栈，
 /// 可以明确的使用 `panic!` 宏来让程序马上崩溃退出。
 fn panic_() {
@@ -1081,63 +1091,43 @@ fn iterating_over_results() {
     }
 }
 
-fn main() {
-    // 明确调用 `panic!` 主动退出。
-    // panic_();
-
-    // 使用宏来定义不同的错误行为
-    // abort_and_unwind();
-
-    // 使用 `Option` 来处理错误。
-    // option_and_unwrap();
+rbe::sections! {
+    // 会 `panic!` 的示例默认不会运行，需要在命令行中明确指定，例如 `cargo run -- panic_`
+    #[manual]
+    #[should_panic]
+    panic_: "明确调用 `panic!` 主动退出",
+    abort_and_unwind: "使用宏来定义不同的错误行为",
+    #[manual]
+    #[should_panic]
+    option_and_unwrap: "使用 `Option` 来处理错误",
 
     // `Option` 相关的使用方法
-    {
-        // 使用 `?` 操作符来快捷获取数据。
-        unpacking_options_with_question_mark();
-
-        // `map` 方法只处理有值的情况，并对闭包返回值进行 `Option<T>` 包装。
-        combinators_map();
-
-        // `and_then` 方法只处理有值的情况，不对返回值进行包装，但是要求闭包必须返回一个 `Option<T>` 的包装值。
-        combinators_and_then();
-
-        // 常用的其他方法 `or` `or_else` `get_or_insert` `get_or_insert_with`
-        unpacking_options_and_default();
-    }
+    unpacking_options_with_question_mark: "使用 `?` 操作符来快捷获取数据",
+    combinators_map: "`map` 方法只处理有值的情况，并对闭包返回值进行 `Option<T>` 包装",
+    combinators_and_then: "`and_then` 方法只处理有值的情况，要求闭包必须返回一个 `Option<T>` 的包装值",
+    unpacking_options_and_default: "常用的其他方法 `or` `or_else` `get_or_insert` `get_or_insert_with`",
 
     // `Result` 相关的使用方法
-    {
-        // `Result` 对象的基础介绍
-        result();
-        // 使用 `Result` 定义 `main` 函数的返回值
-        using_result_in_main();
-        // `map` 方法和 `Option` 中的方法一致
-        map_for_result();
-        // 为 `Result` 定义类型别名
-        aliases_for_result();
-        // 提前返回
-        eraly_result();
-        // `?` 操作符简化代码逻辑，以及操作符的前身 `try!` 宏
-        intorducing_question_mark();
-    }
+    result: "`Result` 对象的基础介绍",
+    using_result_in_main: "使用 `Result` 定义 `main` 函数的返回值",
+    map_for_result: "`map` 方法和 `Option` 中的方法一致",
+    aliases_for_result: "为 `Result` 定义类型别名",
+    eraly_result: "提前返回",
+    intorducing_question_mark: "`?` 操作符简化代码逻辑，以及操作符的前身 `try!` 宏",
 
     // 同时处理多种错误类型
-    {
-        // 基础示例
-        multiple_error_types();
-        // `Result` 和 `Option` 类型的互相嵌套使用
-        pulling_results_out_of_options();
-        // 自定义错误类型
-        defining_an_error_type();
-        // 错误类型的装箱，实现多种错误类型并存
-        boxing_errors();
-        // 使用 `?` 优化错误转换代码
-        other_uses_of_question_mark();
-        // 完整的自定义错误类型示例
-        wrapping_errors();
-    }
-
-    // 迭代器中使用 `Result`
-    iterating_over_results();
+    #[manual]
+    #[should_panic]
+    multiple_error_types: "基础示例，空数组和无法转换的数字都会 `panic!`",
+    pulling_results_out_of_options: "`Result` 和 `Option` 类型的互相嵌套使用",
+    defining_an_error_type: "自定义错误类型",
+    boxing_errors: "错误类型的装箱，实现多种错误类型并存",
+    other_uses_of_question_mark: "使用 `?` 优化错误转换代码",
+    wrapping_errors: "完整的自定义错误类型示例",
+
+    iterating_over_results: "迭代器中使用 `Result`",
+}
+
+fn main() {
+    rbe::run(SECTIONS);
 }
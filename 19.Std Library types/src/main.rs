@@ -10,6 +10,14 @@
 //! - 基于堆内存动态分配的指针指针 `Box` 类型：`Box<i32>`
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(
+    dead_code,
+    invalid_from_utf8,
+    unused_variables,
+    clippy::unnecessary_literal_unwrap
+)]
+
 ///
 /// 堆内存动态分配的智能指针 `Box`
 ///
@@ -760,39 +768,24 @@ fn arc() {
     thread::sleep(Duration::from_secs(1));
 }
 
-fn main() {
-    // `Box<T>` 堆内存动态分配的智能指针
-    box_();
-
-    // `Vec<T>` 可变数组
-    vectors();
-
-    // `String` 可变字符串
-    strings();
-
-    // `Option<T>` 枚举
-    option();
-
-    // `Result<T, E>` 枚举
-    result();
-
-    // `Result<T, E>` 和 `?` 表达式。
-    result_and_question_mark();
-
-    // 主动触发异常
-    // panic_();
-
-    // `HashMap<K, V>` 哈希表
-    hash_map();
-    // 自定义哈希表 `key` 类型
-    alternate_custom_key_types();
-
-    // `HashSet<T>` 集合
-    hash_set();
-
-    // `Rc<T>` 引用计数器
-    rc_();
+rbe::sections! {
+    box_: "`Box<T>` 堆内存动态分配的智能指针",
+    vectors: "`Vec<T>` 可变数组",
+    strings: "`String` 可变字符串",
+    option: "`Option<T>` 枚举",
+    result: "`Result<T, E>` 枚举",
+    result_and_question_mark: "`Result<T, E>` 和 `?` 表达式",
+    // 会 `panic!` 的示例默认不会运行，需要在命令行中明确指定
+    #[manual]
+    #[should_panic]
+    panic_: "主动触发异常",
+    hash_map: "`HashMap<K, V>` 哈希表",
+    alternate_custom_key_types: "自定义哈希表 `key` 类型",
+    hash_set: "`HashSet<T>` 集合",
+    rc_: "`Rc<T>` 引用计数器",
+    arc: "`Arc<T>` 多线程引用计数器",
+}
 
-    // `Arc<T>` 多线程引用计数器
-    arc();
+fn main() {
+    rbe::run(SECTIONS);
 }
@@ -0,0 +1,10 @@
+use rbe::snapshot::{self, Snapshot};
+
+#[test]
+fn snapshot() {
+    Snapshot::sections(env!("CARGO_BIN_EXE_std_library_types"))
+        // `HashMap` 和 `HashSet` 的遍历顺序是不确定的
+        .normalize("hash_map", snapshot::sort_lines)
+        .normalize("hash_set", snapshot::sort_items)
+        .check();
+}
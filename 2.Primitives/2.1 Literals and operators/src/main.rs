@@ -1,3 +1,6 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::nonminimal_bool)]
+
 fn main() {
     // 无符号整数加法
     println!("1 + 2 = {}", 1u32 + 2);
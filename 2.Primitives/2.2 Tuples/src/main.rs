@@ -6,19 +6,10 @@ fn reverse(pair: (i32, bool)) -> (bool, i32) {
     (bool_param, int_param)
 }
 
-// 该结构用于下面代码示例
-#[derive(Debug)]
-struct Matrix(f32, f32, f32, f32);
+// 矩阵的定义放在了单独的 `matrix.rs` 文件中，用于下面代码示例
+mod matrix;
 
-impl std::fmt::Display for Matrix {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "( {} {} )\n( {} {} )", self.0, self.1, self.2, self.3)
-    }
-}
-
-fn transpose(matrix: Matrix) -> Matrix {
-    Matrix(matrix.0, matrix.2, matrix.1, matrix.3)
-}
+use matrix::Matrix;
 
 fn main() {
     // 元组可以容纳任意数量的任意类型值
@@ -58,9 +49,36 @@ fn main() {
     let (a, b, c, d) = tuple;
     println!("{:?}, {:?}, {:?}, {:?}", a, b, c, d);
 
-    let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
+    // 矩阵的行数和列数是类型的一部分，数组的数组按行存放数据
+    let matrix = Matrix([[1.1, 1.2], [2.1, 2.2]]);
     println!("{:?}", matrix);
 
     println!("Matrix:\n{}", matrix);
-    println!("Transpose:\n{}", transpose(matrix));
+    println!("Transpose:\n{}", matrix.transpose());
+
+    // 转置以后行数和列数会交换
+    let wide = Matrix([[1, 2, 3], [4, 5, 6]]);
+    println!("Transpose of 2x3:\n{}", wide.transpose());
+
+    // 2x3 的矩阵乘以 3x2 的矩阵得到 2x2 的矩阵
+    let product = wide * wide.transpose();
+    println!("Product:\n{}", product);
+    println!("Sum:\n{}", product + Matrix::identity());
+
+    // 维度不匹配的时候编译不通过
+    // println!("{}", wide * wide);
+    // println!("{}", wide + wide.transpose());
+    // TODO ^ 移除上面两行代码的注释看看编译错误
+
+    // 方阵可以计算行列式和逆矩阵
+    println!("Determinant: {}", product.determinant());
+    let square = Matrix([[4.0, 7.0], [2.0, 6.0]]);
+    match square.inverse() {
+        Some(inverse) => println!("Inverse:\n{:.2}", inverse),
+        None => println!("Matrix is singular"),
+    }
+    println!(
+        "Inverse of singular: {:?}",
+        Matrix([[1.0, 2.0], [2.0, 4.0]]).inverse()
+    );
 }
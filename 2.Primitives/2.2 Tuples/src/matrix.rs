@@ -0,0 +1,288 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// `R` 行 `C` 列的矩阵，数据按行存放
+///
+/// 行数和列数都是类型的一部分，所以 `2×3` 和 `3×2` 是两个不同的类型，
+/// 维度不匹配的加法和乘法在编译的时候就会报错。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<T, const R: usize, const C: usize>(pub [[T; C]; R]);
+
+/// 矩阵元素需要支持的运算
+pub trait Scalar:
+    Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+/// 浮点数，只有浮点数的矩阵才能求逆
+pub trait Float: Scalar + PartialOrd + Neg<Output = Self> {
+    /// 机器精度，也就是 1 和比 1 大的最小的数之间的差
+    const EPSILON: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($zero:literal, $one:literal: $($t:ty),*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+        })*
+    };
+}
+
+impl_scalar!(0, 1: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_scalar!(0.0, 1.0: f32, f64);
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {
+        $(impl Float for $t {
+            const EPSILON: Self = $t::EPSILON;
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+        })*
+    };
+}
+
+impl_float!(f32, f64);
+
+impl<T: Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// 转置，`R×C` 的矩阵变成 `C×R` 的矩阵
+    pub fn transpose(self) -> Matrix<T, C, R> {
+        Matrix(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.0[j][i])
+        }))
+    }
+}
+
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    /// 单位矩阵
+    pub fn identity() -> Self {
+        Matrix(std::array::from_fn(|i| {
+            std::array::from_fn(|j| if i == j { T::ONE } else { T::ZERO })
+        }))
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>, const N: usize> Matrix<T, N, N> {
+    /// 行列式
+    ///
+    /// 使用 Bareiss 算法做消元，每一步的除法都是整除，所以整数矩阵也能得到精确的结果。
+    pub fn determinant(&self) -> T {
+        let mut m = self.0;
+        let mut negative = false;
+        let mut previous = T::ONE;
+
+        for k in 0..N {
+            if m[k][k] == T::ZERO {
+                // 主元是 0 的时候和下面的某一行交换，每次交换行列式变号
+                match (k + 1..N).find(|&i| m[i][k] != T::ZERO) {
+                    Some(i) => {
+                        m.swap(i, k);
+                        negative = !negative;
+                    }
+                    None => return T::ZERO,
+                }
+            }
+            for i in k + 1..N {
+                for j in k + 1..N {
+                    m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous;
+                }
+            }
+            previous = m[k][k];
+        }
+
+        // `0×0` 矩阵的行列式是 1
+        let det = if N == 0 { T::ONE } else { m[N - 1][N - 1] };
+        if negative {
+            -det
+        } else {
+            det
+        }
+    }
+}
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    /// 逆矩阵，奇异矩阵没有逆矩阵，返回 `None`
+    ///
+    /// 使用列主元的高斯-约当消元法。
+    ///
+    /// 消元的时候有舍入误差，奇异矩阵的主元也可能是一个很小的数而不是 0，所以判断主元是不是 0
+    /// 需要和这一列原来的大小比较，而不是和一个固定的值比较：主元的绝对值不超过
+    /// `N * EPSILON * 这一列最大的绝对值` 的时候认为矩阵是奇异的。
+    /// 这样元素都很小的矩阵也可以求逆，元素很大的奇异矩阵也不会因为舍入误差被当做可逆的。
+    pub fn inverse(&self) -> Option<Self> {
+        let mut m = self.0;
+        let mut inverse = Self::identity().0;
+
+        // 每一列的容差，`T` 不能直接从 `usize` 转换，所以把最大值累加 `N` 次
+        let tolerance: [T; N] = std::array::from_fn(|k| {
+            let largest = (0..N)
+                .map(|i| m[i][k].abs())
+                .fold(T::ZERO, |a, b| if b > a { b } else { a });
+            (0..N).fold(T::ZERO, |sum, _| sum + largest) * T::EPSILON
+        });
+
+        for k in 0..N {
+            // 选出这一列中绝对值最大的元素作为主元，减小误差
+            let pivot = (k..N).max_by(|&a, &b| {
+                m[a][k]
+                    .abs()
+                    .partial_cmp(&m[b][k].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+            // 使用 `<=`，整列都是 0 的时候容差也是 0
+            if m[pivot][k].abs() <= tolerance[k] {
+                return None;
+            }
+            m.swap(pivot, k);
+            inverse.swap(pivot, k);
+
+            let scale = m[k][k];
+            for j in 0..N {
+                m[k][j] = m[k][j] / scale;
+                inverse[k][j] = inverse[k][j] / scale;
+            }
+
+            for i in (0..N).filter(|&i| i != k) {
+                let factor = m[i][k];
+                for j in 0..N {
+                    m[i][j] = m[i][j] - factor * m[k][j];
+                    inverse[i][j] = inverse[i][j] - factor * inverse[k][j];
+                }
+            }
+        }
+
+        Some(Matrix(inverse))
+    }
+}
+
+// 只有行数和列数都相同的矩阵才能相加
+impl<T: Scalar, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Matrix(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.0[i][j] + rhs.0[i][j])
+        }))
+    }
+}
+
+// `R×K` 的矩阵只能乘以 `K×C` 的矩阵，结果是 `R×C` 的矩阵
+impl<T: Scalar, const R: usize, const K: usize, const C: usize> Mul<Matrix<T, K, C>>
+    for Matrix<T, R, K>
+{
+    type Output = Matrix<T, R, C>;
+
+    fn mul(self, rhs: Matrix<T, K, C>) -> Matrix<T, R, C> {
+        // 结果的第 `i` 行第 `j` 列是左边第 `i` 行和右边第 `j` 列的点积
+        let dot =
+            |i: usize, j: usize| (0..K).fold(T::ZERO, |sum, k| sum + self.0[i][k] * rhs.0[k][j]);
+        Matrix(std::array::from_fn(|i| std::array::from_fn(|j| dot(i, j))))
+    }
+}
+
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Matrix<T, R, C> {
+    // 每一行输出成 `( a b )`，行与行之间换行
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str("(")?;
+            for value in row {
+                f.write_str(" ")?;
+                // 把 `Formatter` 交给元素，这样 `{:.2}` 这类精度设置会用在每一个元素上
+                fmt::Display::fmt(value, f)?;
+            }
+            f.write_str(" )")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_rows() {
+        let matrix = Matrix([[1.1, 1.2], [2.1, 2.2]]);
+        assert_eq!(matrix.to_string(), "( 1.1 1.2 )\n( 2.1 2.2 )");
+        assert_eq!(
+            format!("{:.2}", Matrix([[1.0, 2.0, 3.0]])),
+            "( 1.00 2.00 3.00 )"
+        );
+    }
+
+    #[test]
+    fn transpose_changes_shape() {
+        let matrix = Matrix([[1, 2, 3], [4, 5, 6]]);
+        let transposed: Matrix<i32, 3, 2> = matrix.transpose();
+        assert_eq!(transposed, Matrix([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(transposed.transpose(), matrix);
+    }
+
+    #[test]
+    fn add_and_mul() {
+        let a = Matrix([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix([[7, 8], [9, 10], [11, 12]]);
+        assert_eq!(a * b, Matrix([[58, 64], [139, 154]]));
+        assert_eq!(a + a, Matrix([[2, 4, 6], [8, 10, 12]]));
+        assert_eq!(Matrix::identity() * b, b);
+        assert_eq!(b * Matrix::identity(), b);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(Matrix([[1, 2], [3, 4]]).determinant(), -2);
+        assert_eq!(Matrix([[0, 1], [1, 0]]).determinant(), -1);
+        assert_eq!(
+            Matrix([[2, -3, 1], [2, 0, -1], [1, 4, 5]]).determinant(),
+            49
+        );
+        assert_eq!(Matrix([[1, 2], [2, 4]]).determinant(), 0);
+        assert_eq!(Matrix::<i64, 4, 4>::identity().determinant(), 1);
+        assert_eq!(Matrix::<i32, 0, 0>([]).determinant(), 1);
+        assert_eq!(Matrix([[0.5, 1.5], [2.0, 4.0]]).determinant(), -1.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Matrix([[4.0, 7.0], [2.0, 6.0]]);
+        let inverse = matrix.inverse().unwrap();
+        let product = matrix * inverse;
+        for (i, row) in product.0.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-12, "{}", product);
+            }
+        }
+
+        // 第一列的主元是 0，需要先交换行
+        let swap = Matrix([[0.0, 1.0], [1.0, 0.0]]);
+        assert_eq!(swap.inverse(), Some(swap));
+
+        assert_eq!(Matrix([[1.0, 2.0], [2.0, 4.0]]).inverse(), None);
+        assert_eq!(Matrix([[1.0, 0.0], [0.0, 0.0]]).inverse(), None);
+    }
+
+    #[test]
+    fn inverse_tolerance_scales_with_columns() {
+        // 元素很小但是可逆的矩阵，固定的容差会把它当做奇异矩阵
+        let small = Matrix([[1e-20, 0.0], [0.0, 2e-20]]);
+        assert_eq!(small.inverse(), Some(Matrix([[1e20, 0.0], [0.0, 0.5e20]])));
+        let mixed = Matrix([[1.0, 0.0], [0.0, 1e-30_f32]]);
+        assert!(mixed.inverse().is_some());
+
+        // 元素很大的奇异矩阵，第二行是第一行的 1/3，舍入误差会留下一个很小但不是 0 的主元
+        let third = 1e10 / 3.0;
+        let large = Matrix([[3e10, 1e10], [1e10, third]]);
+        assert_eq!(large.determinant(), 0.0);
+        assert_eq!(large.inverse(), None);
+    }
+}
@@ -1,9 +1,22 @@
+use std::fmt::Display;
 use std::mem;
 
-// 该函数借用数组切片（slice）
-fn analyze_slice(slice: &[i32]) {
-    println!("First element of the slice: {}", slice[0]);
-    println!("The slice has {} elements", slice.len());
+// 切片的统计信息放在了单独的 `stats.rs` 文件中
+mod stats;
+
+use stats::{Number, SliceStats};
+
+// 该函数借用数组切片（slice），整数和浮点数的切片都可以
+fn analyze_slice<T: Number + Display>(slice: &[T]) {
+    // 空切片没有第一个元素，直接使用 `slice[0]` 会 `panic!`
+    match SliceStats::new(slice) {
+        Some(stats) => {
+            println!("First element of the slice: {}", slice[0]);
+            println!("The slice has {} elements", stats.len);
+            println!("Stats: {:.2}", stats);
+        }
+        None => println!("The slice is empty"),
+    }
 }
 
 fn main() {
@@ -38,6 +51,21 @@ fn main() {
     assert_eq!(&empty_array, &[]); // 对空数组创建一个切片引用，实际上这个等于下面的代码。
     assert_eq!(&empty_array, &[][..]); // 对空数组创建切片引用，引用范围为整个数组。
 
+    // 空切片不会 `panic!`
+    analyze_slice(&empty_array);
+
+    // 浮点数的切片也可以统计
+    analyze_slice(&[2.5, -1.0, 0.125]);
+
+    // 每三个连续元素组成一个窗口，可以计算移动平均数
+    for (i, window) in SliceStats::windows(&xs, 3).enumerate() {
+        println!("Window {}: mean {:.1}", i, window.mean);
+    }
+    // 每两个元素分成一组，最后一组只有一个元素
+    for (i, chunk) in SliceStats::chunks(&xs, 2).enumerate() {
+        println!("Chunk {}: {}", i, chunk);
+    }
+
     // 数组还可以通过 `.get()` 方法安全的访问，该方法会返回一个 `Option`
     // 可以通过 `.expect()` 方法来输出一个明确的错误。
     for i in 0..xs.len() + 1 {
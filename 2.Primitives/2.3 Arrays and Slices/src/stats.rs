@@ -0,0 +1,257 @@
+use std::fmt;
+use std::mem;
+use std::ops::Add;
+
+/// 可以统计的数字类型，所有的整数和浮点数都实现了这个特性
+pub trait Number: Copy + PartialOrd + Add<Output = Self> {
+    /// 加法溢出的时候返回 `None`，浮点数不会溢出
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// 整数永远不是 `NaN`
+    fn is_nan(self) -> bool;
+
+    /// 计算平均数的时候统一转换成 `f64`
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn is_nan(self) -> bool {
+                false
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                Some(self + other)
+            }
+
+            fn is_nan(self) -> bool {
+                <$t>::is_nan(self)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_float!(f32, f64);
+
+/// 切片中元素的排列顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// 所有元素都相等，只有一个元素的切片也是这种情况
+    Constant,
+    /// 从小到大排列，允许相邻的元素相等
+    Ascending,
+    /// 从大到小排列，允许相邻的元素相等
+    Descending,
+    Unsorted,
+}
+
+/// 一个切片的统计信息
+///
+/// 浮点数切片中有 `NaN` 的时候，和浮点数的运算规则一样，`min`、`max`、`sum` 和 `mean` 都是 `NaN`，
+/// `order` 是 [`Order::Unsorted`]。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliceStats<T> {
+    pub len: usize,
+    pub min: T,
+    pub max: T,
+    /// 整数求和溢出的时候是 `None`
+    pub sum: Option<T>,
+    pub mean: f64,
+    pub order: Order,
+    /// `mem::size_of_val` 得到的切片占用的字节数
+    pub bytes: usize,
+}
+
+impl<T: Number> SliceStats<T> {
+    /// 统计切片的信息，空切片没有最大值和最小值，返回 `None`
+    pub fn new(slice: &[T]) -> Option<Self> {
+        let (&first, rest) = slice.split_first()?;
+
+        let mut min = first;
+        let mut max = first;
+        let mut sum = Some(first);
+        let mut total = first.to_f64();
+        for &x in rest {
+            // 和 `NaN` 比较的结果总是 `false`，所以 `min` 变成 `NaN` 以后就不会再改变
+            if x < min || x.is_nan() {
+                min = x;
+            }
+            if x > max || x.is_nan() {
+                max = x;
+            }
+            sum = sum.and_then(|sum| sum.checked_add(x));
+            total += x.to_f64();
+        }
+
+        Some(SliceStats {
+            len: slice.len(),
+            min,
+            max,
+            sum,
+            mean: total / slice.len() as f64,
+            order: order(slice),
+            bytes: mem::size_of_val(slice),
+        })
+    }
+
+    /// 每 `size` 个连续元素组成的窗口的统计信息，窗口之间是重叠的，例如用来计算移动平均数
+    ///
+    /// `size` 为 0 或者大于切片长度的时候没有任何窗口。
+    pub fn windows(slice: &[T], size: usize) -> impl Iterator<Item = SliceStats<T>> + '_ {
+        // 标准库的 `windows(0)` 会 `panic!`，所以需要先检查
+        (size > 0)
+            .then(|| slice.windows(size))
+            .into_iter()
+            .flatten()
+            .filter_map(SliceStats::new)
+    }
+
+    /// 把切片分成每 `size` 个元素一组，每组的统计信息，最后一组的元素可能不足 `size` 个
+    ///
+    /// `size` 为 0 的时候没有任何分组。
+    pub fn chunks(slice: &[T], size: usize) -> impl Iterator<Item = SliceStats<T>> + '_ {
+        (size > 0)
+            .then(|| slice.chunks(size))
+            .into_iter()
+            .flatten()
+            .filter_map(SliceStats::new)
+    }
+}
+
+// 相邻的两个元素依次比较，同时记录是否出现过上升和下降
+fn order<T: PartialOrd>(slice: &[T]) -> Order {
+    let mut ascending = true;
+    let mut descending = true;
+    for pair in slice.windows(2) {
+        match pair[0].partial_cmp(&pair[1]) {
+            Some(std::cmp::Ordering::Less) => descending = false,
+            Some(std::cmp::Ordering::Greater) => ascending = false,
+            Some(std::cmp::Ordering::Equal) => {}
+            // 和 `NaN` 比较的结果是 `None`，这种情况不算有序
+            None => return Order::Unsorted,
+        }
+    }
+
+    match (ascending, descending) {
+        (true, true) => Order::Constant,
+        (true, false) => Order::Ascending,
+        (false, true) => Order::Descending,
+        (false, false) => Order::Unsorted,
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SliceStats<T> {
+    // `{:.2}` 这类精度设置会用在平均数上，其它的数字原样输出
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "len={} min={} max={} sum=", self.len, self.min, self.max)?;
+        match &self.sum {
+            Some(sum) => write!(f, "{}", sum)?,
+            None => f.write_str("overflow")?,
+        }
+        match f.precision() {
+            Some(precision) => write!(f, " mean={:.*}", precision, self.mean)?,
+            None => write!(f, " mean={}", self.mean)?,
+        }
+        write!(f, " order={:?} bytes={}", self.order, self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_slice_is_none() {
+        assert_eq!(SliceStats::<i32>::new(&[]), None);
+        assert_eq!(SliceStats::<f64>::new(&[]), None);
+    }
+
+    #[test]
+    fn integer_stats() {
+        let stats = SliceStats::new(&[3, 1, 4, 1, 5]).unwrap();
+        assert_eq!(stats.len, 5);
+        assert_eq!((stats.min, stats.max), (1, 5));
+        assert_eq!(stats.sum, Some(14));
+        assert_eq!(stats.mean, 2.8);
+        assert_eq!(stats.order, Order::Unsorted);
+        assert_eq!(stats.bytes, 20);
+        assert_eq!(
+            format!("{:.1}", stats),
+            "len=5 min=1 max=5 sum=14 mean=2.8 order=Unsorted bytes=20"
+        );
+    }
+
+    #[test]
+    fn sum_overflow_keeps_mean() {
+        let stats = SliceStats::new(&[100u8, 200, 250]).unwrap();
+        assert_eq!(stats.sum, None);
+        assert_eq!(stats.mean, 550.0 / 3.0);
+        assert_eq!(stats.order, Order::Ascending);
+        assert_eq!(stats.bytes, 3);
+    }
+
+    #[test]
+    fn float_stats_propagate_nan() {
+        // `NaN` 在开头、中间和结尾的时候结果都一样
+        for xs in [
+            [f64::NAN, 2.5, -1.0],
+            [2.5, f64::NAN, -1.0],
+            [2.5, -1.0, f64::NAN],
+        ] {
+            let stats = SliceStats::new(&xs).unwrap();
+            assert!(stats.min.is_nan() && stats.max.is_nan());
+            assert!(stats.sum.unwrap().is_nan());
+            assert!(stats.mean.is_nan());
+            assert_eq!(stats.order, Order::Unsorted);
+        }
+
+        let stats = SliceStats::new(&[2.5, -1.0]).unwrap();
+        assert_eq!((stats.min, stats.max), (-1.0, 2.5));
+
+        let stats = SliceStats::new(&[0.5f32, 0.5]).unwrap();
+        assert_eq!(stats.order, Order::Constant);
+        assert_eq!(stats.sum, Some(1.0));
+    }
+
+    #[test]
+    fn order_detection() {
+        assert_eq!(order(&[1]), Order::Constant);
+        assert_eq!(order(&[1, 2, 2, 3]), Order::Ascending);
+        assert_eq!(order(&[3, 3, 1]), Order::Descending);
+        assert_eq!(order(&[1, 3, 2]), Order::Unsorted);
+    }
+
+    #[test]
+    fn windows_and_chunks() {
+        let xs = [1, 2, 3, 4, 5];
+        let means: Vec<f64> = SliceStats::windows(&xs, 3).map(|s| s.mean).collect();
+        assert_eq!(means, [2.0, 3.0, 4.0]);
+        assert_eq!(SliceStats::windows(&xs, 0).count(), 0);
+        assert_eq!(SliceStats::windows(&xs, 6).count(), 0);
+
+        let sums: Vec<Option<i32>> = SliceStats::chunks(&xs, 2).map(|s| s.sum).collect();
+        assert_eq!(sums, [Some(3), Some(7), Some(5)]);
+        assert_eq!(SliceStats::chunks(&xs, 0).count(), 0);
+        assert_eq!(SliceStats::<i32>::chunks(&[], 2).count(), 0);
+    }
+}
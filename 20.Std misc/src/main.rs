@@ -8,6 +8,18 @@
 //! 这些扩展方法提供了更多操作系统底层的能力。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(
+    dead_code,
+    non_snake_case,
+    clippy::manual_flatten,
+    clippy::needless_borrow,
+    clippy::needless_borrows_for_generic_args,
+    clippy::redundant_static_lifetimes,
+    clippy::single_match,
+    clippy::suspicious_open_options
+)]
+
 ///
 /// 线程
 /// `Rust` 提供了一种机制来可以直接调用系统的线程能力，`Rust` 线程和系统线程是 `1:1` 的关系，
@@ -618,29 +630,21 @@ fn foreign_function_interface() {
     println!("cos({:?}) = {:?}", z, cos(z));
 }
 
+rbe::sections! {
+    threads: "线程操作",
+    thread_test_case_map_reduce: "线程小测验",
+    channels: "通道",
+    path_: "文件路径",
+    file_I_O: "文件IO",
+    child_processes: "子进程",
+    pipes: "子进程和管道",
+    wait: "等待子进程",
+    filesystem_operations: "操作文件系统",
+    program_arguments: "进程参数",
+    arguments_parsing: "进程参数的使用",
+    foreign_function_interface: "外部函数使用 `FFI`",
+}
+
 fn main() {
-    // 线程操作
-    threads();
-    // 线程小测验
-    thread_test_case_map_reduce();
-    // 通道
-    channels();
-    // 文件路径
-    path_();
-    // 文件IO
-    file_I_O();
-    // 子进程
-    child_processes();
-    // 子进程和管道
-    pipes();
-    // 等待子进程
-    wait();
-    // 操作文件系统
-    filesystem_operations();
-    // 进程参数
-    program_arguments();
-    // 进程参数的使用
-    arguments_parsing();
-    // 外部函数使用 `FFI`
-    foreign_function_interface();
+    rbe::run(SECTIONS);
 }
@@ -0,0 +1,12 @@
+use rbe::snapshot::{self, Snapshot};
+
+#[test]
+fn snapshot() {
+    Snapshot::sections(env!("CARGO_BIN_EXE_std_misc"))
+        // 多线程的执行顺序是不确定的
+        .normalize("threads", snapshot::sort_lines)
+        .normalize("channels", snapshot::sort_items)
+        // 编译器的版本和运行环境相关
+        .normalize("child_processes", snapshot::mask("rustc 1."))
+        .check();
+}
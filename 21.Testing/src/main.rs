@@ -10,6 +10,9 @@
 //! 测试使用的依赖项 (Dev-dependencies)
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::empty_line_after_doc_comments)]
+
 /// 测试是 `Rust` 的函数，用来测试一些非测试代码的正确性，有些测试函数是为了初始化一些环境，
 /// 在测试函数中可以使用断言来验证结果是否符合预期
 ///
@@ -12,6 +12,9 @@
 //! - 实现一些不安全的特性
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(asm_sub_register)]
+
 ///
 /// 原始指针使用 `*` 操作符来访问，和 `&T` 中解引用的行为类似，
 /// 但是引用始终都是安全的并且保证引用的数据永远都是有效的，并且会被编译使用借用规则来校验。
@@ -424,30 +427,25 @@ fn options() {
     }
 }
 
-fn main() {
-    // 操作原始指针
-    raw_pointers();
-    // 调用函数
-    calling_unsafe_functions();
+rbe::sections! {
+    raw_pointers: "操作原始指针",
+    calling_unsafe_functions: "调用函数",
+
+    // 内联汇编
+    inline_assembly: "内联汇编",
+    late_output_operands: "寄存器独占",
+    // `out` 是特权指令，在用户态运行会直接触发段错误，所以默认不运行
+    #[manual]
+    #[ignore = "`out` 指令在用户态会触发段错误"]
+    explicit_register_operands: "明确指明寄存器 平台相关",
+    clobbered_registers: "标记寄存器的状态",
+    symbol_operands_and_abi_clobbers: "快捷标记寄存器状态，通过调用约定来标记",
+    register_template_modifiers: "通过模版字符串来精细化操作寄存器",
+    memory_address_operands: "内存指针访问，平台相关",
+    labels: "汇编中的命名标签",
+    options: "汇编的优化配置项",
+}
 
-    {
-        // 内联汇编
-        inline_assembly();
-        // 寄存器独占
-        late_output_operands();
-        // 明确指明寄存器 平台相关
-        explicit_register_operands();
-        // 标记寄存器的状态
-        clobbered_registers();
-        // 快捷标记寄存器状态，通过调用约定来标记
-        symbol_operands_and_abi_clobbers();
-        // 通过模版字符串来精细化操作寄存器
-        register_template_modifiers();
-        // 内存指针访问，平台相关
-        memory_address_operands();
-        // 汇编中的命名标签
-        labels();
-        // 汇编的优化配置项
-        options();
-    }
+fn main() {
+    rbe::run(SECTIONS);
 }
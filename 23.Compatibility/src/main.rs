@@ -3,6 +3,9 @@
 //! 已经在尽可能的保证向前兼容，但是依旧有些特性会出现改变。
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::empty_line_after_doc_comments, clippy::needless_doctest_main)]
+
 ///
 /// Rust 和许多其他的编程语言一样也拥有关键字的概念，这些关键字都对 Rust 有特定的功能，
 /// 所以你不能使用这些关键字来定义变量或者函数或者其他的一些东西，原始标记（Raw identifiers）
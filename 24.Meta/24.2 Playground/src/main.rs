@@ -12,6 +12,9 @@
 //! 更多的相关属性可以[查看这里](https://doc.rust-lang.org/rustdoc/the-doc-attribute.html)
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(dead_code)]
+
 ///
 ///
 /// 通过加上额外的标记 `rust,editable` 来让 mdbook 识别要生成交互的文档测试代码块
@@ -0,0 +1,348 @@
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// 两个字段的结构
+// 实现了 `Copy` 以后，点就可以像数字一样直接复制
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    /// 两点之间的距离
+    pub fn distance(self, other: Point) -> f64 {
+        let d = other - self;
+        (d.x as f64).hypot(d.y as f64)
+    }
+}
+
+// 点的加减法就是对应坐标的加减法，可以把点当做向量使用
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: f32) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+// 结构体可以嵌套使用
+// 和屏幕坐标一样，`y` 轴向下，所以左上角的 `x` 和 `y` 都比右下角的小
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    // 长方形可以使用 top、left 和 bottom、right 两个点来表示
+    pub top_left: Point,
+    pub bottom_right: Point,
+}
+
+impl Rectangle {
+    /// 使用任意两个对角的点创建长方形，结果总是规范的（见 [`Rectangle::normalize`]）
+    pub fn new(a: Point, b: Point) -> Rectangle {
+        Rectangle {
+            top_left: a,
+            bottom_right: b,
+        }
+        .normalize()
+    }
+
+    /// 边长为 `size` 的正方形，和 [`Rectangle::new`] 一样结果总是规范的，
+    /// `size` 是负数的时候正方形在 `top_left` 的左上方
+    pub fn square(top_left: &Point, size: f32) -> Rectangle {
+        Rectangle {
+            // 复制 Point 结构体
+            top_left: Point { ..*top_left },
+            // 按照尺寸对左上角的坐标进行扩大
+            bottom_right: Point {
+                x: top_left.x + size,
+                y: top_left.y + size,
+            },
+        }
+        .normalize()
+    }
+
+    /// 两个角是否是颠倒的，例如 `top_left` 实际上在 `bottom_right` 的右边或者下边
+    pub fn is_inverted(&self) -> bool {
+        self.top_left.x > self.bottom_right.x || self.top_left.y > self.bottom_right.y
+    }
+
+    /// 交换颠倒的坐标，保证 `top_left` 在左上角，`bottom_right` 在右下角
+    pub fn normalize(&self) -> Rectangle {
+        let Rectangle {
+            top_left: a,
+            bottom_right: b,
+        } = *self;
+        Rectangle {
+            top_left: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            bottom_right: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        (self.bottom_right.x - self.top_left.x).abs()
+    }
+
+    pub fn height(&self) -> f32 {
+        (self.bottom_right.y - self.top_left.y).abs()
+    }
+
+    /// 直接使用两个角的坐标计算面积
+    ///
+    /// 两个角颠倒的时候宽或者高是负数，得到的面积没有意义，这个时候返回 `None`，
+    /// 需要先调用 [`Rectangle::normalize`]，或者使用总是返回正数的 [`HasArea::area`]。
+    pub fn rect_area(&self) -> Option<f64> {
+        if self.is_inverted() {
+            return None;
+        }
+        // 解构两个坐标点
+        let Rectangle {
+            top_left,
+            bottom_right,
+        } = self;
+        // 计算宽高
+        let width = bottom_right.x - top_left.x;
+        let height = bottom_right.y - top_left.y;
+        // 计算面积
+        Some(width as f64 * height as f64)
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        2.0 * (self.width() as f64 + self.height() as f64)
+    }
+
+    /// 点是否在长方形内，边上的点也算在内
+    pub fn contains(&self, point: Point) -> bool {
+        let r = self.normalize();
+        (r.top_left.x..=r.bottom_right.x).contains(&point.x)
+            && (r.top_left.y..=r.bottom_right.y).contains(&point.y)
+    }
+
+    /// 两个长方形重叠的部分，不重叠的时候返回 `None`
+    ///
+    /// 只有一条边或者一个角接触的时候，结果是宽或者高为 0 的长方形。
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let (a, b) = (self.normalize(), other.normalize());
+        let top_left = Point::new(
+            a.top_left.x.max(b.top_left.x),
+            a.top_left.y.max(b.top_left.y),
+        );
+        let bottom_right = Point::new(
+            a.bottom_right.x.min(b.bottom_right.x),
+            a.bottom_right.y.min(b.bottom_right.y),
+        );
+        let rect = Rectangle {
+            top_left,
+            bottom_right,
+        };
+        // 不重叠的时候计算出来的两个角是颠倒的
+        if rect.is_inverted() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+
+    /// 同时包含两个长方形的最小的长方形
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        let (a, b) = (self.normalize(), other.normalize());
+        Rectangle {
+            top_left: Point::new(
+                a.top_left.x.min(b.top_left.x),
+                a.top_left.y.min(b.top_left.y),
+            ),
+            bottom_right: Point::new(
+                a.bottom_right.x.max(b.bottom_right.x),
+                a.bottom_right.y.max(b.bottom_right.y),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f32,
+}
+
+impl Circle {
+    /// 周长
+    pub fn perimeter(&self) -> f64 {
+        2.0 * PI * self.radius.abs() as f64
+    }
+
+    /// 圆周上的点也算在内
+    pub fn contains(&self, point: Point) -> bool {
+        self.center.distance(point) <= self.radius.abs() as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point,
+}
+
+impl Triangle {
+    pub fn perimeter(&self) -> f64 {
+        self.a.distance(self.b) + self.b.distance(self.c) + self.c.distance(self.a)
+    }
+}
+
+// 和第 14.4 章中的 `HasArea` 一样的特性，所有的图形都可以计算面积
+pub trait HasArea {
+    fn area(&self) -> f64;
+}
+
+// 颠倒的长方形先规范化，所以面积总是正数
+impl HasArea for Rectangle {
+    fn area(&self) -> f64 {
+        self.width() as f64 * self.height() as f64
+    }
+}
+
+impl HasArea for Circle {
+    fn area(&self) -> f64 {
+        let r = self.radius as f64;
+        PI * r * r
+    }
+}
+
+impl HasArea for Triangle {
+    // 鞋带公式（shoelace formula），顶点是顺时针还是逆时针排列会影响符号，所以取绝对值
+    fn area(&self) -> f64 {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let cross = ab.x as f64 * ac.y as f64 - ab.y as f64 * ac.x as f64;
+        cross.abs() / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x1: f32, y1: f32, x2: f32, y2: f32) -> Rectangle {
+        Rectangle {
+            top_left: Point::new(x1, y1),
+            bottom_right: Point::new(x2, y2),
+        }
+    }
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(1.0, 2.0);
+        let b = Point::new(4.0, 6.0);
+        assert_eq!(a + b, Point::new(5.0, 8.0));
+        assert_eq!(b - a, Point::new(3.0, 4.0));
+        assert_eq!(a * 2.0, Point::new(2.0, 4.0));
+        assert_eq!(-a, Point::new(-1.0, -2.0));
+        assert_eq!(a.distance(b), 5.0);
+    }
+
+    #[test]
+    fn inverted_rectangle() {
+        let inverted = rect(10.0, 10.0, 0.0, 5.0);
+        assert!(inverted.is_inverted());
+        assert_eq!(inverted.rect_area(), None);
+        assert_eq!(inverted.area(), 50.0);
+        assert_eq!(inverted.normalize(), rect(0.0, 5.0, 10.0, 10.0));
+        assert_eq!(inverted.normalize().rect_area(), Some(50.0));
+        assert_eq!(
+            Rectangle::new(Point::new(10.0, 10.0), Point::new(0.0, 5.0)),
+            inverted.normalize()
+        );
+        assert_eq!(inverted.perimeter(), 30.0);
+    }
+
+    #[test]
+    fn squares() {
+        let origin = Point::new(1.0, 2.0);
+        assert_eq!(Rectangle::square(&origin, 3.0), rect(1.0, 2.0, 4.0, 5.0));
+        // 负数的边长也会得到规范的正方形
+        let square = Rectangle::square(&origin, -3.0);
+        assert!(!square.is_inverted());
+        assert_eq!(square, rect(-2.0, -1.0, 1.0, 2.0));
+        assert_eq!(square.rect_area(), Some(9.0));
+    }
+
+    #[test]
+    fn contains_points() {
+        let r = rect(0.0, 0.0, 10.0, 5.0);
+        assert!(r.contains(Point::new(5.0, 2.5)));
+        assert!(r.contains(Point::new(10.0, 5.0)));
+        assert!(!r.contains(Point::new(10.5, 2.0)));
+        // 颠倒的长方形也可以判断
+        assert!(rect(10.0, 5.0, 0.0, 0.0).contains(Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        let b = rect(5.0, 5.0, 15.0, 20.0);
+        assert_eq!(a.intersection(&b), Some(rect(5.0, 5.0, 10.0, 10.0)));
+        assert_eq!(a.union(&b), rect(0.0, 0.0, 15.0, 20.0));
+
+        // 只有一条边接触
+        let touching = rect(10.0, 0.0, 20.0, 10.0);
+        assert_eq!(a.intersection(&touching).map(|r| r.area()), Some(0.0));
+
+        let far = rect(20.0, 20.0, 30.0, 30.0);
+        assert_eq!(a.intersection(&far), None);
+        assert_eq!(far.union(&a), rect(0.0, 0.0, 30.0, 30.0));
+    }
+
+    #[test]
+    fn shapes_have_area() {
+        let circle = Circle {
+            center: Point::default(),
+            radius: 2.0,
+        };
+        assert_eq!(circle.area(), 4.0 * PI);
+        assert_eq!(circle.perimeter(), 4.0 * PI);
+        assert!(circle.contains(Point::new(0.0, 2.0)));
+        assert!(!circle.contains(Point::new(2.0, 2.0)));
+
+        let triangle = Triangle {
+            a: Point::new(0.0, 0.0),
+            b: Point::new(4.0, 0.0),
+            c: Point::new(0.0, 3.0),
+        };
+        assert_eq!(triangle.area(), 6.0);
+        assert_eq!(triangle.perimeter(), 12.0);
+        // 顶点的顺序不影响面积
+        let reversed = Triangle {
+            a: triangle.c,
+            b: triangle.b,
+            c: triangle.a,
+        };
+        assert_eq!(reversed.area(), 6.0);
+    }
+}
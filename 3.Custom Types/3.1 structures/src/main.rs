@@ -1,3 +1,5 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::redundant_field_names)]
 // 禁用未使用的代码警告
 #![allow(dead_code)]
 
@@ -14,47 +16,10 @@ struct Unit;
 // 元组结构
 struct Pair(i32, f32);
 
-// 两个字段的结构
-#[derive(Debug)]
-struct Point {
-    x: f32,
-    y: f32,
-}
-
-// 结构体可以嵌套使用
-#[derive(Debug)]
-struct Rectangle {
-    // 长方形可以使用 top、left 和 bottom、right 两个点来表示
-    top_left: Point,
-    bottom_right: Point,
-}
+// `Point` `Rectangle` 以及其它的图形放在了单独的 `geometry.rs` 文件中
+mod geometry;
 
-impl Rectangle {
-    fn rect_area(&self) -> f64 {
-        // 解构两个坐标点
-        let Rectangle {
-            top_left,
-            bottom_right,
-        } = self;
-        // 计算宽高
-        let width = bottom_right.x - top_left.x;
-        let height = bottom_right.y - top_left.y;
-        // 计算面积
-        width as f64 * height as f64
-    }
-
-    fn square(top_left: &Point, size: f32) -> Rectangle {
-        Rectangle {
-            // 复制 Point 结构体
-            top_left: Point { ..*top_left },
-            // 按照尺寸对左上角的坐标进行扩大
-            bottom_right: Point {
-                x: top_left.x + size,
-                y: top_left.y + size,
-            },
-        }
-    }
-}
+use geometry::{Circle, HasArea, Point, Rectangle, Triangle};
 
 fn main() {
     let name = String::from("Peter");
@@ -113,5 +78,41 @@ fn main() {
     let square = Rectangle::square(&point, 100f32);
 
     println!("square: \n{:#?}", square);
-    println!("square area: {}", square.rect_area());
+    println!("square area: {:?}", square.rect_area());
+
+    // 点可以像向量一样加减
+    let offset = Point::new(1.0, 1.0);
+    println!("point + offset: {:?}", point + offset);
+
+    // 两个角颠倒的长方形直接计算面积是没有意义的
+    let inverted = Rectangle {
+        top_left: Point::new(10.0, 10.0),
+        bottom_right: Point::new(0.0, 5.0),
+    };
+    println!("inverted area: {:?}", inverted.rect_area());
+    println!("normalized: {:?}", inverted.normalize());
+    println!("normalized area: {:?}", inverted.normalize().rect_area());
+
+    let a = Rectangle::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    let b = Rectangle::new(Point::new(5.0, 5.0), Point::new(15.0, 20.0));
+    println!("a contains (5, 5): {}", a.contains(Point::new(5.0, 5.0)));
+    println!("a contains (11, 5): {}", a.contains(Point::new(11.0, 5.0)));
+    println!("intersection: {:?}", a.intersection(&b));
+    println!("union: {:?}", a.union(&b));
+    println!("perimeter of b: {}", b.perimeter());
+
+    // 所有的图形都实现了 `HasArea` 特性
+    let circle = Circle {
+        center: Point::default(),
+        radius: 1.5,
+    };
+    let triangle = Triangle {
+        a: Point::new(0.0, 0.0),
+        b: Point::new(4.0, 0.0),
+        c: Point::new(0.0, 3.0),
+    };
+    let shapes: [&dyn HasArea; 4] = [&a, &inverted, &circle, &triangle];
+    for shape in shapes {
+        println!("area: {:.3}", shape.area());
+    }
 }
@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// 使用枚举类型对web事件进行分类。
+// 枚举的每一项都是独一无二的，枚举的每一项都可以包含任意数据
+// 但是枚举的每一项都属于该枚举类型
+// `PageLoad != PageUnload` 和 `KeyPress(char) != Paste(String)` 都是成立的
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebEvent {
+    // 枚举的每一项都是一个独立的
+    PageLoad,
+    PageUnload,
+    // 使用类似元组结构来保存数据
+    KeyPress(char),
+    Paste(String),
+    // 或者使用结构体保存数据
+    Click { x: i64, y: i64 },
+}
+
+/// 事件的种类，也就是去掉了数据的 `WebEvent`，用来按照种类注册事件处理函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    PageLoad,
+    PageUnload,
+    KeyPress,
+    Paste,
+    Click,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 5] = [
+        EventKind::PageLoad,
+        EventKind::PageUnload,
+        EventKind::KeyPress,
+        EventKind::Paste,
+        EventKind::Click,
+    ];
+
+    /// 文本格式中使用的名字
+    pub fn name(self) -> &'static str {
+        match self {
+            EventKind::PageLoad => "load",
+            EventKind::PageUnload => "unload",
+            EventKind::KeyPress => "key",
+            EventKind::Paste => "paste",
+            EventKind::Click => "click",
+        }
+    }
+}
+
+impl WebEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            WebEvent::PageLoad => EventKind::PageLoad,
+            WebEvent::PageUnload => EventKind::PageUnload,
+            // 只关心是哪一项，不关心里面的数据
+            WebEvent::KeyPress(_) => EventKind::KeyPress,
+            WebEvent::Paste(_) => EventKind::Paste,
+            WebEvent::Click { .. } => EventKind::Click,
+        }
+    }
+}
+
+/// 注册事件处理函数或者过滤条件的时候返回的编号，用来取消注册
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+type Handler = Box<dyn FnMut(&WebEvent)>;
+type Filter = Box<dyn Fn(&WebEvent) -> bool>;
+
+/// 事件总线，按照事件的种类把事件分发给注册的处理函数
+#[derive(Default)]
+pub struct EventBus {
+    // 每种事件的处理函数按照注册的顺序保存
+    handlers: HashMap<EventKind, Vec<(HandlerId, Handler)>>,
+    filters: Vec<(HandlerId, Filter)>,
+    next_id: u64,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        EventBus::default()
+    }
+
+    fn next_id(&mut self) -> HandlerId {
+        self.next_id += 1;
+        HandlerId(self.next_id)
+    }
+
+    /// 注册一种事件的处理函数，同一种事件的处理函数按照注册的顺序调用
+    pub fn on(&mut self, kind: EventKind, handler: impl FnMut(&WebEvent) + 'static) -> HandlerId {
+        let id = self.next_id();
+        self.handlers
+            .entry(kind)
+            .or_default()
+            .push((id, Box::new(handler)));
+        id
+    }
+
+    /// 添加一个过滤条件，只有满足所有过滤条件的事件才会分发给处理函数
+    pub fn filter(&mut self, filter: impl Fn(&WebEvent) -> bool + 'static) -> HandlerId {
+        let id = self.next_id();
+        self.filters.push((id, Box::new(filter)));
+        id
+    }
+
+    /// 取消注册处理函数或者过滤条件，编号不存在的时候返回 `false`
+    pub fn off(&mut self, id: HandlerId) -> bool {
+        let before = self.filters.len();
+        self.filters.retain(|(filter, _)| *filter != id);
+        if self.filters.len() != before {
+            return true;
+        }
+
+        for handlers in self.handlers.values_mut() {
+            if let Some(index) = handlers.iter().position(|(handler, _)| *handler == id) {
+                // 使用 `remove` 而不是 `swap_remove`，保持剩下的处理函数的顺序
+                drop(handlers.remove(index));
+                return true;
+            }
+        }
+        false
+    }
+
+    /// 分发一个事件，返回调用了多少个处理函数，被过滤掉的事件返回 0
+    pub fn dispatch(&mut self, event: &WebEvent) -> usize {
+        if !self.filters.iter().all(|(_, filter)| filter(event)) {
+            return 0;
+        }
+
+        match self.handlers.get_mut(&event.kind()) {
+            Some(handlers) => {
+                for (_, handler) in handlers.iter_mut() {
+                    handler(event);
+                }
+                handlers.len()
+            }
+            None => 0,
+        }
+    }
+
+    /// 依次分发多个事件，例如重放记录下来的操作，返回一共调用了多少次处理函数
+    pub fn replay<'a>(&mut self, events: impl IntoIterator<Item = &'a WebEvent>) -> usize {
+        events.into_iter().map(|event| self.dispatch(event)).sum()
+    }
+}
+
+// 每个事件输出成一行文本，例如 `click 20 80` `key x` `paste my text`
+// 粘贴的文本中的 `\` 和换行会被转义，保证一个事件只占一行
+impl fmt::Display for WebEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.kind().name();
+        match self {
+            WebEvent::PageLoad | WebEvent::PageUnload => f.write_str(name),
+            WebEvent::KeyPress(c) => write!(f, "{} {}", name, escape(&c.to_string())),
+            WebEvent::Paste(s) => write!(f, "{} {}", name, escape(s)),
+            WebEvent::Click { x, y } => write!(f, "{} {} {}", name, x, y),
+        }
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => result.push('\\'),
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(result)
+}
+
+/// 解析事件时可能出现的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEventError {
+    /// 不认识的事件名字
+    UnknownEvent(String),
+    /// 事件的参数不正确
+    InvalidArguments(String),
+}
+
+impl fmt::Display for ParseEventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseEventError::UnknownEvent(s) => write!(f, "unknown event `{}`", s),
+            ParseEventError::InvalidArguments(s) => write!(f, "invalid arguments in `{}`", s),
+        }
+    }
+}
+
+impl Error for ParseEventError {}
+
+/// 解析 `{}` 输出的一行文本
+impl FromStr for WebEvent {
+    type Err = ParseEventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseEventError::InvalidArguments(s.to_string());
+
+        // 名字和参数之间只有一个空格，按键和粘贴的参数原样保留，因为空格本身也可能是参数的一部分
+        let (name, args) = match s.split_once(' ') {
+            Some((name, args)) => (name, Some(args)),
+            None => (s, None),
+        };
+        let kind = EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| ParseEventError::UnknownEvent(name.to_string()))?;
+
+        match (kind, args) {
+            (EventKind::PageLoad, None) => Ok(WebEvent::PageLoad),
+            (EventKind::PageUnload, None) => Ok(WebEvent::PageUnload),
+            (EventKind::KeyPress, Some(args)) => {
+                let key = unescape(args).ok_or_else(invalid)?;
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(WebEvent::KeyPress(c)),
+                    _ => Err(invalid()),
+                }
+            }
+            (EventKind::Paste, Some(args)) => {
+                Ok(WebEvent::Paste(unescape(args).ok_or_else(invalid)?))
+            }
+            (EventKind::Click, Some(args)) => {
+                let mut numbers = args.split_whitespace().map(str::parse::<i64>);
+                match (numbers.next(), numbers.next(), numbers.next()) {
+                    (Some(Ok(x)), Some(Ok(y)), None) => Ok(WebEvent::Click { x, y }),
+                    _ => Err(invalid()),
+                }
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// 解析记录文件时的错误，带有出错的行号（从 1 开始）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSessionError {
+    pub line: usize,
+    pub error: ParseEventError,
+}
+
+impl fmt::Display for ParseSessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl Error for ParseSessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// 把多个事件记录成文本，每个事件一行
+pub fn record<'a>(events: impl IntoIterator<Item = &'a WebEvent>) -> String {
+    events
+        .into_iter()
+        .map(|event| format!("{}\n", event))
+        .collect()
+}
+
+/// 解析记录下来的文本，空行和 `#` 开头的注释行会被忽略
+pub fn parse_session(text: &str) -> Result<Vec<WebEvent>, ParseSessionError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            line.parse().map_err(|error| ParseSessionError {
+                line: index + 1,
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    const SESSION: &str = "\
+# 记录下来的一次操作
+load
+key x
+key  
+paste my text
+paste C:\\\\temp\\nsecond line
+click 20 80
+click -5 0
+
+unload
+";
+
+    fn events() -> Vec<WebEvent> {
+        vec![
+            WebEvent::PageLoad,
+            WebEvent::KeyPress('x'),
+            WebEvent::KeyPress(' '),
+            WebEvent::Paste("my text".to_string()),
+            WebEvent::Paste("C:\\temp\nsecond line".to_string()),
+            WebEvent::Click { x: 20, y: 80 },
+            WebEvent::Click { x: -5, y: 0 },
+            WebEvent::PageUnload,
+        ]
+    }
+
+    #[test]
+    fn text_round_trip() {
+        assert_eq!(parse_session(SESSION), Ok(events()));
+        assert_eq!(parse_session(&record(&events())), Ok(events()));
+        assert_eq!(WebEvent::Click { x: 20, y: 80 }.to_string(), "click 20 80");
+    }
+
+    #[test]
+    fn parse_errors_report_line() {
+        assert_eq!(
+            parse_session("load\n\nscroll 3\n"),
+            Err(ParseSessionError {
+                line: 3,
+                error: ParseEventError::UnknownEvent("scroll".to_string()),
+            })
+        );
+        for line in [
+            "click 1",
+            "click 1 2 3",
+            "key xy",
+            "key",
+            "load now",
+            "paste \\t",
+        ] {
+            assert_eq!(
+                line.parse::<WebEvent>(),
+                Err(ParseEventError::InvalidArguments(line.to_string())),
+                "{}",
+                line
+            );
+        }
+    }
+
+    // 把每次调用记录到共享的日志里
+    fn logger(log: &Rc<RefCell<Vec<String>>>, name: &'static str) -> impl FnMut(&WebEvent) {
+        let log = Rc::clone(log);
+        move |event| log.borrow_mut().push(format!("{}: {}", name, event))
+    }
+
+    #[test]
+    fn dispatch_in_registration_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        bus.on(EventKind::Click, logger(&log, "first"));
+        bus.on(EventKind::KeyPress, logger(&log, "keys"));
+        bus.on(EventKind::Click, logger(&log, "second"));
+
+        let calls = bus.replay(&parse_session(SESSION).unwrap());
+        assert_eq!(calls, 6);
+        assert_eq!(
+            *log.borrow(),
+            [
+                "keys: key x",
+                "keys: key  ",
+                "first: click 20 80",
+                "second: click 20 80",
+                "first: click -5 0",
+                "second: click -5 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_handlers_and_filters() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        let first = bus.on(EventKind::Click, logger(&log, "first"));
+        bus.on(EventKind::Click, logger(&log, "second"));
+        let positive = bus.filter(|event| !matches!(event, WebEvent::Click { x, .. } if *x < 0));
+
+        assert!(bus.off(first));
+        assert!(!bus.off(first));
+        assert_eq!(bus.dispatch(&WebEvent::Click { x: -1, y: 0 }), 0);
+        assert_eq!(bus.dispatch(&WebEvent::Click { x: 1, y: 0 }), 1);
+
+        assert!(bus.off(positive));
+        assert_eq!(bus.dispatch(&WebEvent::Click { x: -1, y: 0 }), 1);
+        assert_eq!(bus.dispatch(&WebEvent::PageLoad), 0);
+        assert_eq!(*log.borrow(), ["second: click 1 0", "second: click -1 0"]);
+    }
+}
@@ -0,0 +1,439 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// 二元运算符，也就是 `example02` 中的 `VeryVerboseEnumOfThingsToDoWithNumbers` 加上乘法和除法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Op {
+    /// 使用 `checked_*` 方法计算，溢出和除以 0 的时候返回错误而不是 `panic!`
+    pub fn run(&self, x: i64, y: i64) -> Result<i64, EvalError> {
+        match self {
+            Self::Add => x.checked_add(y).ok_or(EvalError::Overflow),
+            Self::Subtract => x.checked_sub(y).ok_or(EvalError::Overflow),
+            Self::Multiply => x.checked_mul(y).ok_or(EvalError::Overflow),
+            Self::Divide if y == 0 => Err(EvalError::DivisionByZero),
+            // `i64::MIN / -1` 也会溢出
+            Self::Divide => x.checked_div(y).ok_or(EvalError::Overflow),
+        }
+    }
+
+    // 优先级，数字越大越先计算
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Subtract => 1,
+            Self::Multiply | Self::Divide => 2,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Subtract => '-',
+            Self::Multiply => '*',
+            Self::Divide => '/',
+        }
+    }
+}
+
+// 取负的优先级比所有的二元运算都高，数字和括号的优先级最高
+const NEG_PRECEDENCE: u8 = 3;
+const ATOM_PRECEDENCE: u8 = 4;
+
+/// 表达式树
+///
+/// 数字都是非负数，负数使用 `Neg` 表示，例如 `-5` 解析成 `Neg(Num(5))`，
+/// 所以输出以后再解析可以得到同样的表达式树。
+/// `Num` 使用 `u64` 保存，这样 `-9223372036854775808` 也就是 `i64::MIN` 可以写出来。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Num(u64),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn binary(op: Op, left: Expr, right: Expr) -> Expr {
+        Expr::Binary(op, Box::new(left), Box::new(right))
+    }
+
+    pub fn neg(expr: Expr) -> Expr {
+        Expr::Neg(Box::new(expr))
+    }
+
+    /// 计算表达式的值
+    pub fn eval(&self) -> Result<i64, EvalError> {
+        match self {
+            Expr::Num(n) => i64::try_from(*n).map_err(|_| EvalError::Overflow),
+            Expr::Neg(expr) => match **expr {
+                // 直接对数字取负，`i64::MIN` 的绝对值超出了 `i64` 的范围但是结果没有溢出
+                Expr::Num(n) => 0_i64.checked_sub_unsigned(n).ok_or(EvalError::Overflow),
+                _ => expr.eval()?.checked_neg().ok_or(EvalError::Overflow),
+            },
+            Expr::Binary(op, left, right) => op.run(left.eval()?, right.eval()?),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Num(_) => ATOM_PRECEDENCE,
+            Expr::Neg(_) => NEG_PRECEDENCE,
+            Expr::Binary(op, _, _) => op.precedence(),
+        }
+    }
+}
+
+/// 计算表达式时可能出现的错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalError {
+    Overflow,
+    DivisionByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Overflow => f.write_str("arithmetic overflow"),
+            EvalError::DivisionByZero => f.write_str("division by zero"),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+// 子表达式的优先级低于 `min` 的时候才需要加括号，这样输出的括号最少
+fn write_operand(f: &mut fmt::Formatter<'_>, expr: &Expr, min: u8) -> fmt::Result {
+    if expr.precedence() < min {
+        write!(f, "({})", expr)
+    } else {
+        write!(f, "{}", expr)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Neg(expr) => {
+                f.write_str("-")?;
+                write_operand(f, expr, NEG_PRECEDENCE)
+            }
+            // 所有的二元运算都是左结合的，`1 - 2 - 3` 等于 `(1 - 2) - 3`，
+            // 所以右边的子表达式和当前运算的优先级相同的时候也需要括号，例如 `1 - (2 - 3)`
+            Expr::Binary(op, left, right) => {
+                write_operand(f, left, op.precedence())?;
+                write!(f, " {} ", op.symbol())?;
+                write_operand(f, right, op.precedence() + 1)
+            }
+        }
+    }
+}
+
+/// 解析表达式时可能出现的错误，位置是从 0 开始的字节偏移
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseExprError {
+    /// 不认识的字符
+    InvalidChar(usize, char),
+    /// 数字超出了 `u64` 的范围
+    NumberTooLarge(usize),
+    /// 括号或者 `-` 嵌套的层数超过了 [`MAX_DEPTH`]
+    TooDeep(usize),
+    /// 出现了不应该出现的符号，例如 `1 + * 2` 中的 `*`
+    UnexpectedToken(usize),
+    /// 表达式不完整，例如 `1 +` 或者 `(1`
+    UnexpectedEnd,
+}
+
+impl fmt::Display for ParseExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseExprError::InvalidChar(pos, c) => {
+                write!(f, "invalid character `{}` at {}", c, pos)
+            }
+            ParseExprError::NumberTooLarge(pos) => write!(f, "number too large at {}", pos),
+            ParseExprError::TooDeep(pos) => write!(f, "expression nested too deeply at {}", pos),
+            ParseExprError::UnexpectedToken(pos) => write!(f, "unexpected token at {}", pos),
+            ParseExprError::UnexpectedEnd => f.write_str("unexpected end of expression"),
+        }
+    }
+}
+
+impl Error for ParseExprError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Num(u64),
+    Op(Op),
+    LeftParen,
+    RightParen,
+}
+
+// 把字符串切分成一个个的符号，同时记录每个符号的位置
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, ParseExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Op(Op::Add),
+            '-' => Token::Op(Op::Subtract),
+            '*' => Token::Op(Op::Multiply),
+            '/' => Token::Op(Op::Divide),
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '0'..='9' => {
+                let mut end = pos + 1;
+                while let Some(&(i, '0'..='9')) = chars.peek() {
+                    end = i + 1;
+                    chars.next();
+                }
+                let n = s[pos..end]
+                    .parse()
+                    .map_err(|_| ParseExprError::NumberTooLarge(pos))?;
+                Token::Num(n)
+            }
+            c => return Err(ParseExprError::InvalidChar(pos, c)),
+        };
+        tokens.push((pos, token));
+    }
+
+    Ok(tokens)
+}
+
+/// 括号和 `-` 最多嵌套的层数
+///
+/// 解析是递归进行的，每一层嵌套都会占用一部分栈空间，`((((…` 这样的输入如果不限制层数会导致栈溢出。
+pub const MAX_DEPTH: usize = 128;
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // 当前嵌套的层数
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.next).map(|&(_, token)| token)
+    }
+
+    fn bump(&mut self) -> Result<(usize, Token), ParseExprError> {
+        let token = *self
+            .tokens
+            .get(self.next)
+            .ok_or(ParseExprError::UnexpectedEnd)?;
+        self.next += 1;
+        Ok(token)
+    }
+
+    // 优先级爬升（precedence climbing）
+    // 先解析一个操作数，然后只要后面的运算符的优先级不低于 `min`，就把它和右边的操作数合并起来，
+    // 右边的操作数只能包含优先级更高的运算，这样相同优先级的运算就是左结合的
+    fn expr(&mut self, min: u8) -> Result<Expr, ParseExprError> {
+        let mut left = self.unary()?;
+        while let Some(Token::Op(op)) = self.peek() {
+            if op.precedence() < min {
+                break;
+            }
+            self.next += 1;
+            let right = self.expr(op.precedence() + 1)?;
+            left = Expr::binary(op, left, right);
+        }
+        Ok(left)
+    }
+
+    // 数字、括号以及前面带有 `-` 的操作数
+    fn unary(&mut self) -> Result<Expr, ParseExprError> {
+        let (pos, token) = self.bump()?;
+        if let Token::Num(n) = token {
+            return Ok(Expr::Num(n));
+        }
+        // 剩下的情况都需要递归，先检查嵌套的层数
+        if self.depth == MAX_DEPTH {
+            return Err(ParseExprError::TooDeep(pos));
+        }
+        self.depth += 1;
+        let expr = match token {
+            Token::Op(Op::Subtract) => self.unary().map(Expr::neg),
+            Token::LeftParen => self.expr(0).and_then(|expr| match self.bump()? {
+                (_, Token::RightParen) => Ok(expr),
+                (pos, _) => Err(ParseExprError::UnexpectedToken(pos)),
+            }),
+            _ => Err(ParseExprError::UnexpectedToken(pos)),
+        };
+        self.depth -= 1;
+        expr
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            next: 0,
+            depth: 0,
+        };
+        let expr = parser.expr(0)?;
+        // 所有的符号都应该用完了，例如 `1 2` 或者 `1)` 是错误的
+        match parser.tokens.get(parser.next) {
+            Some(&(pos, _)) => Err(ParseExprError::UnexpectedToken(pos)),
+            None => Ok(expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn parse(s: &str) -> Expr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(parse("1 + 2 * 3").eval(), Ok(7));
+        assert_eq!(parse("(1 + 2) * 3").eval(), Ok(9));
+        assert_eq!(parse("10 - 4 - 3").eval(), Ok(3));
+        assert_eq!(parse("100 / 10 / 5").eval(), Ok(2));
+        assert_eq!(parse("-2 * -(3 - 5)").eval(), Ok(-4));
+        assert_eq!(parse("--7").eval(), Ok(7));
+    }
+
+    #[test]
+    fn minimal_parentheses() {
+        for (input, output) in [
+            ("((1)) + (2 * 3)", "1 + 2 * 3"),
+            ("(1 + 2) * 3", "(1 + 2) * 3"),
+            ("(1 - 2) - 3", "1 - 2 - 3"),
+            ("1 - (2 - 3)", "1 - (2 - 3)"),
+            ("1 + (2 + 3)", "1 + (2 + 3)"),
+            ("8 / (4 / 2)", "8 / (4 / 2)"),
+            ("-(1 + 2)", "-(1 + 2)"),
+            ("(-1) * -(2)", "-1 * -2"),
+            ("-(2 * 3)", "-(2 * 3)"),
+        ] {
+            assert_eq!(parse(input).to_string(), output, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "1 + x".parse::<Expr>(),
+            Err(ParseExprError::InvalidChar(4, 'x'))
+        );
+        assert_eq!(
+            "99999999999999999999".parse::<Expr>(),
+            Err(ParseExprError::NumberTooLarge(0))
+        );
+        assert_eq!(
+            "1 + * 2".parse::<Expr>(),
+            Err(ParseExprError::UnexpectedToken(4))
+        );
+        assert_eq!(
+            "1 2".parse::<Expr>(),
+            Err(ParseExprError::UnexpectedToken(2))
+        );
+        assert_eq!("(1 + 2".parse::<Expr>(), Err(ParseExprError::UnexpectedEnd));
+        assert_eq!(
+            "18446744073709551616".parse::<Expr>(),
+            Err(ParseExprError::NumberTooLarge(0))
+        );
+        assert_eq!("".parse::<Expr>(), Err(ParseExprError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(parse("1 / 0").eval(), Err(EvalError::DivisionByZero));
+        assert_eq!(parse("1 / (2 - 2)").eval(), Err(EvalError::DivisionByZero));
+        assert_eq!(
+            parse("9223372036854775807 + 1").eval(),
+            Err(EvalError::Overflow)
+        );
+        assert_eq!(
+            parse("(-9223372036854775807 - 1) / -1").eval(),
+            Err(EvalError::Overflow)
+        );
+        assert_eq!(
+            parse("-(-9223372036854775807 - 1)").eval(),
+            Err(EvalError::Overflow)
+        );
+        assert_eq!(parse("-9223372036854775808").eval(), Ok(i64::MIN));
+        assert_eq!(
+            parse("9223372036854775808").eval(),
+            Err(EvalError::Overflow)
+        );
+        assert_eq!(
+            parse("--9223372036854775808").eval(),
+            Err(EvalError::Overflow)
+        );
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(parse(&nested(MAX_DEPTH)).eval(), Ok(1));
+        assert_eq!(
+            nested(MAX_DEPTH + 1).parse::<Expr>(),
+            Err(ParseExprError::TooDeep(MAX_DEPTH))
+        );
+        assert_eq!(
+            "-".repeat(MAX_DEPTH + 1).parse::<Expr>(),
+            Err(ParseExprError::TooDeep(MAX_DEPTH))
+        );
+        // 很深的输入不会栈溢出，遇到第一个超出限制的括号就会返回错误
+        let error = "(".repeat(1_000_000).parse::<Expr>().unwrap_err();
+        assert_eq!(error, ParseExprError::TooDeep(MAX_DEPTH));
+        assert_eq!(
+            error.to_string(),
+            format!("expression nested too deeply at {}", MAX_DEPTH)
+        );
+    }
+
+    // 随机的数字，大部分比较小，也会有接近 `u64` 边界的大数字
+    fn random_num(rng: &mut StdRng) -> u64 {
+        match rng.gen_range(0..4) {
+            0 => rng.gen(),
+            1 => u64::MAX - rng.gen_range(0..=1000),
+            _ => rng.gen_range(0..=1000),
+        }
+    }
+
+    // 随机生成一个表达式树，负数使用 `Neg` 表示，和解析的结果保持一致
+    fn random_expr(rng: &mut StdRng, depth: u32) -> Expr {
+        match rng.gen_range(0..if depth == 0 { 2 } else { 7 }) {
+            0 => Expr::Num(random_num(rng)),
+            // 负数的字面量
+            1 => Expr::neg(Expr::Num(random_num(rng))),
+            2 => Expr::neg(random_expr(rng, depth - 1)),
+            n => {
+                let op = [Op::Add, Op::Subtract, Op::Multiply, Op::Divide][n - 3];
+                Expr::binary(op, random_expr(rng, depth - 1), random_expr(rng, depth - 1))
+            }
+        }
+    }
+
+    #[test]
+    fn parse_display_round_trip() {
+        // 固定随机数种子，每次运行生成的表达式都一样，失败的时候可以复现
+        let mut rng = StdRng::seed_from_u64(0x3_2);
+        for _ in 0..2000 {
+            let expr = random_expr(&mut rng, 6);
+            let text = expr.to_string();
+            assert_eq!(text.parse::<Expr>(), Ok(expr.clone()), "{}", text);
+            assert_eq!(parse(&text).eval(), expr.eval(), "{}", text);
+        }
+    }
+}
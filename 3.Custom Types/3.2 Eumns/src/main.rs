@@ -1,19 +1,16 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(dead_code, unused_variables)]
+
+// `WebEvent` 枚举以及基于它的事件分发放在了单独的 `events.rs` 文件中
+mod events;
+// `example02` 中的运算枚举扩展成的表达式求值放在了单独的 `expr.rs` 文件中
+mod expr;
+
+use events::{EventBus, EventKind, WebEvent};
+use expr::Expr;
+
 fn example01() {
-    // 使用枚举类型对web事件进行分类。
-    // 枚举的每一项都是独一无二的，枚举的每一项都可以包含任意数据
-    // 但是枚举的每一项都属于该枚举类型
-    // `PageLoad != PageUnload` 和 `KeyPress(char) != Paste(String)` 都是成立的
-
-    enum WebEvent {
-        // 枚举的每一项都是一个独立的
-        PageLoad,
-        PageUnload,
-        // 使用类似元组结构来保存数据
-        KeyPress(char),
-        Paste(String),
-        // 或者使用结构体保存数据
-        Click { x: i64, y: i64 },
-    }
+    // `WebEvent` 的定义参考 `events.rs`
 
     // 该函数接收 `WebEvent` 枚举，也就是说可以接受该枚举下定义的任意一项数据
     fn inspect(event: WebEvent) {
@@ -67,7 +64,99 @@ fn example02() {
     let x = Operations::Add;
 }
 
+fn event_bus() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let mut bus = EventBus::new();
+
+    // 按照事件的种类注册处理函数，同一种事件的处理函数按照注册的顺序调用
+    bus.on(EventKind::Click, |event| {
+        println!("first click handler: {:?}", event)
+    });
+    let second = bus.on(EventKind::Click, |event| {
+        if let WebEvent::Click { x, y } = event {
+            println!("second click handler: ({}, {})", x, y);
+        }
+    });
+
+    // 处理函数是闭包，可以通过 `Rc<Cell>` 记录状态
+    let keys = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&keys);
+    bus.on(EventKind::KeyPress, move |_| counter.set(counter.get() + 1));
+
+    // 过滤掉粘贴的空白内容
+    bus.filter(|event| !matches!(event, WebEvent::Paste(s) if s.trim().is_empty()));
+    bus.on(EventKind::Paste, |event| {
+        println!("paste handler: {}", event)
+    });
+
+    // 每个事件都可以记录成一行文本，再解析回来用于重放
+    let session = events::record(&[
+        WebEvent::PageLoad,
+        WebEvent::KeyPress('x'),
+        WebEvent::Paste("my text".to_owned()),
+        WebEvent::Paste("\n".to_owned()),
+        WebEvent::Click { x: 20, y: 80 },
+        WebEvent::KeyPress('y'),
+        WebEvent::PageUnload,
+    ]);
+    print!("recorded session:\n{}", session);
+
+    let events = events::parse_session(&session).unwrap();
+    println!("handler calls: {}", bus.replay(&events));
+    println!("keys pressed: {}", keys.get());
+
+    // 取消注册以后就不会再调用了
+    bus.off(second);
+    let click = "click 1 2".parse::<WebEvent>().unwrap();
+    println!("handler calls after off: {}", bus.dispatch(&click));
+
+    // 解析错误会带上行号
+    match events::parse_session("load\nscroll 3\n") {
+        Ok(events) => println!("parsed: {:?}", events),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn calculator() {
+    // 字符串解析成表达式树，输出的时候只保留必要的括号
+    for text in [
+        "1 + 2 * 3",
+        "((1 + 2)) * (3)",
+        "10 - (4 - 3) - 2",
+        "-(2 * 3) / -4",
+        "7 / (3 - 3)",
+        "9223372036854775807 + 1",
+        "2 * (3 + 4",
+        "2 ^ 3",
+    ] {
+        match text.parse::<Expr>() {
+            Ok(expr) => match expr.eval() {
+                Ok(value) => println!("{:>24} => {} = {}", text, expr, value),
+                Err(e) => println!("{:>24} => {} = error: {}", text, expr, e),
+            },
+            Err(e) => println!("{:>24} => parse error: {}", text, e),
+        }
+    }
+
+    // 也可以直接构造表达式树
+    let expr = Expr::binary(
+        expr::Op::Subtract,
+        Expr::Num(1),
+        Expr::binary(expr::Op::Add, Expr::Num(2), Expr::neg(Expr::Num(3))),
+    );
+    println!("{:?}", expr);
+    println!("{} = {:?}", expr, expr.eval());
+}
+
+rbe::sections! {
+    example01: "枚举的基础使用",
+    example02: "类型别名",
+    event_bus: "基于枚举的事件分发",
+    calculator: "基于枚举的表达式求值",
+}
+
 fn main() {
-    example01();
-    example02();
+    rbe::run(SECTIONS);
 }
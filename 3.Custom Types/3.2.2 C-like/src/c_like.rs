@@ -0,0 +1,210 @@
+use std::error::Error;
+use std::fmt;
+
+/// 数字没有对应的枚举项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownValue {
+    /// 枚举的名字
+    pub enum_name: &'static str,
+    pub value: i32,
+}
+
+impl fmt::Display for UnknownValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} has no variant with value {}",
+            self.enum_name, self.value
+        )
+    }
+}
+
+impl Error for UnknownValue {}
+
+/// 名字没有对应的枚举项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownName {
+    pub enum_name: &'static str,
+    pub name: String,
+}
+
+impl fmt::Display for UnknownName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} has no variant named `{}`", self.enum_name, self.name)
+    }
+}
+
+impl Error for UnknownName {}
+
+/// 定义一个 C 风格的枚举（所有的枚举项都没有数据），同时生成下面这些功能：
+///
+/// - `TryFrom<i32>`，没有对应的枚举项的时候返回 [`UnknownValue`]
+/// - 使用枚举项的名字实现 `Display` 和 `FromStr`，`FromStr` 的错误是 [`UnknownName`]
+/// - `ALL` 常量，按照定义的顺序包含所有的枚举项
+/// - `next` 和 `prev` 方法，按照定义的顺序获取下一项和上一项
+///
+/// 枚举项可以明确指明值，也可以和普通的枚举一样使用默认的值。
+/// 生成的枚举使用 `#[repr(i32)]`，超出 `i32` 范围的值在编译的时候就会报错，
+/// 所以 `TryFrom<i32>` 中的 `as i32` 不会截断。
+/// 枚举和枚举项上都可以添加属性，例如文档注释或者 `#[default]`。
+///
+/// ```ignore
+/// c_like_enum! {
+///     enum Color {
+///         Red = 0xff0000,
+///         Green = 0x00ff00,
+///     }
+/// }
+/// ```
+macro_rules! c_like_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident $(= $value:expr)?),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(i32)]
+        $vis enum $name {
+            $($(#[$vmeta])* $variant $(= $value)?),+
+        }
+
+        impl $name {
+            /// 所有的枚举项，按照定义的顺序排列
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+
+            /// 枚举项的名字
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant)),+
+                }
+            }
+
+            // 当前枚举项在 `ALL` 中的位置
+            fn index(self) -> usize {
+                Self::ALL
+                    .iter()
+                    .position(|&variant| variant == self)
+                    .expect("every variant is listed in ALL")
+            }
+
+            /// 下一个枚举项，最后一项没有下一项
+            pub fn next(self) -> Option<$name> {
+                Self::ALL.get(self.index() + 1).copied()
+            }
+
+            /// 上一个枚举项，第一项没有上一项
+            pub fn prev(self) -> Option<$name> {
+                self.index().checked_sub(1).map(|index| Self::ALL[index])
+            }
+        }
+
+        impl std::convert::TryFrom<i32> for $name {
+            type Error = $crate::c_like::UnknownValue;
+
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                // 枚举项强制转换成数字以后再比较
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|&variant| variant as i32 == value)
+                    .ok_or($crate::c_like::UnknownValue {
+                        enum_name: stringify!($name),
+                        value,
+                    })
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                // 使用 `pad` 输出，这样 `{:>8}` 这类宽度和对齐的设置也可以使用
+                f.pad(self.name())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::c_like::UnknownName;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|variant| variant.name() == s)
+                    .ok_or_else(|| $crate::c_like::UnknownName {
+                        enum_name: stringify!($name),
+                        name: s.to_string(),
+                    })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    c_like_enum! {
+        enum Color {
+            Red = 0xff0000,
+            Green = 0x00ff00,
+            Blue = 0x0000ff,
+        }
+    }
+
+    c_like_enum! {
+        /// 枚举项上也可以使用属性
+        #[derive(Default)]
+        enum Level {
+            /// 比默认值低
+            Low = -1,
+            #[default]
+            Normal,
+            High = 0x10,
+        }
+    }
+
+    #[test]
+    fn try_from_hex_discriminants() {
+        assert_eq!(Color::try_from(0xff0000), Ok(Color::Red));
+        assert_eq!(Color::try_from(0x00ff00), Ok(Color::Green));
+        assert_eq!(Color::try_from(0x0000ff), Ok(Color::Blue));
+
+        let error = Color::try_from(0xffffff).unwrap_err();
+        assert_eq!(error.enum_name, "Color");
+        assert_eq!(error.value, 0xffffff);
+        assert_eq!(
+            error.to_string(),
+            "Color has no variant with value 16777215"
+        );
+    }
+
+    #[test]
+    fn implicit_discriminants_follow_previous() {
+        assert_eq!(Level::try_from(-1), Ok(Level::Low));
+        assert_eq!(Level::try_from(0), Ok(Level::Normal));
+        assert_eq!(Level::try_from(16), Ok(Level::High));
+        assert!(Level::try_from(1).is_err());
+        assert_eq!(Level::default(), Level::Normal);
+        assert_eq!(std::mem::size_of::<Level>(), std::mem::size_of::<i32>());
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for &color in Color::ALL {
+            assert_eq!(color.to_string().parse(), Ok(color));
+        }
+        assert_eq!(format!("{:>6}|", Color::Red), "   Red|");
+
+        let error = "red".parse::<Color>().unwrap_err();
+        assert_eq!(error.to_string(), "Color has no variant named `red`");
+    }
+
+    #[test]
+    fn all_next_prev() {
+        assert_eq!(Color::ALL, [Color::Red, Color::Green, Color::Blue]);
+        assert_eq!(Color::Red.next(), Some(Color::Green));
+        assert_eq!(Color::Blue.next(), None);
+        assert_eq!(Color::Blue.prev(), Some(Color::Green));
+        assert_eq!(Color::Red.prev(), None);
+        assert_eq!(Level::Low.next(), Some(Level::Normal));
+    }
+}
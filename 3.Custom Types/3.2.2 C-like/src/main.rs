@@ -1,18 +1,29 @@
 // 禁用未使用的代码警告
 #![allow(dead_code)]
 
+// `c_like_enum!` 宏定义在单独的 `c_like.rs` 文件中，`#[macro_use]` 让宏在后面的代码中都可以使用
+#[macro_use]
+mod c_like;
+
 // 当不指明任意类型的时候，枚举项默认从 0 开始
-enum Number {
-    Zero,
-    One,
-    Two,
+c_like_enum! {
+    enum Number {
+        Zero,
+        One,
+        Two,
+    }
 }
 
 // 明确指明枚举项的值
-enum Color {
-    Red = 0xff0000,
-    Green = 0x00ff00,
-    Blue = 0x0000ff,
+c_like_enum! {
+    enum Color {
+        Red = 0xff0000,
+        Green = 0x00ff00,
+        Blue = 0x0000ff,
+        // 超出 `i32` 范围的值不能通过编译
+        // Big = 0x1_0000_0000,
+        // TODO ^ 移除注释查看错误
+    }
 }
 
 fn main() {
@@ -22,4 +33,32 @@ fn main() {
 
     println!("roses are #{:06x}", Color::Red as i32);
     println!("violets are #{:06x}", Color::Blue as i32);
+
+    // 数字不能直接转换成枚举，因为不是每个数字都有对应的枚举项，所以使用 `TryFrom`
+    for value in [2, 3] {
+        match Number::try_from(value) {
+            Ok(number) => println!("{} is {}", value, number),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+    println!("{:?}", Color::try_from(0x00ff00));
+    println!("{:?}", Color::try_from(0x123456));
+
+    // 通过名字输出和解析
+    println!("{:?}", "Blue".parse::<Color>());
+    println!("{:?}", "Purple".parse::<Color>());
+
+    // 按照定义的顺序遍历所有的枚举项
+    for color in Color::ALL {
+        println!("{:>5} = #{:06x}", color, *color as i32);
+    }
+
+    // 依次获取下一项
+    let mut number = Some(Number::Zero);
+    while let Some(n) = number {
+        print!("{} ", n);
+        number = n.next();
+    }
+    println!();
+    println!("before Two is {:?}", Number::Two.prev());
 }
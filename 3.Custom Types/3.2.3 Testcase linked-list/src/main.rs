@@ -1,51 +1,231 @@
-// 把 List 枚举定义的所有项都导入到全局
-use crate::List::*;
-enum List {
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+
+// 使用 `Rc` 共享尾部的链表放在了单独的 `rc_list.rs` 文件中
+mod rc_list;
+
+use rc_list::RcList;
+
+// 把 Node 枚举定义的所有项都导入到全局
+use crate::Node::*;
+
+// 链表的节点
+enum Node<T> {
     // Cons 定义为元组类型，第一个位是值，第二位是下一个值的指针
-    Cons(u32, Box<List>),
+    Cons(T, Box<Node<T>>),
     // Nil 表示链表结束 没有下一项了
     Nil,
 }
 
-impl List {
-    fn new() -> List {
-        Nil
+// 链表本身只是对第一个节点的包装
+// 因为要给链表实现 `Drop`，而实现了 `Drop` 的类型不能在 `match` 中把值移动出来，
+// 所以 `Drop` 实现在外面这一层上，里面的 `Node` 还是可以随意的拆开和组装。
+struct List<T> {
+    head: Node<T>,
+}
+
+impl<T> List<T> {
+    fn new() -> List<T> {
+        List { head: Nil }
     }
 
-    fn prepend(self, elem: u32) -> List {
+    fn prepend(mut self, elem: T) -> List<T> {
         // 生成一个新的 Cons 把当前的链表放到新的 Cons 中
-        Cons(elem, Box::new(self))
+        self.push(elem);
+        self
+    }
+
+    // 在链表的最前面添加一个元素
+    fn push(&mut self, elem: T) {
+        // 不能直接把 `self.head` 移动出来，先用 `Nil` 把它替换出来
+        let head = mem::replace(&mut self.head, Nil);
+        self.head = Cons(elem, Box::new(head));
+    }
+
+    // 移除并返回链表的第一个元素
+    fn pop(&mut self) -> Option<T> {
+        match mem::replace(&mut self.head, Nil) {
+            Cons(elem, next) => {
+                // `*next` 把节点从 `Box` 中移动出来
+                self.head = *next;
+                Some(elem)
+            }
+            Nil => None,
+        }
+    }
+
+    // 查看链表的第一个元素
+    fn peek(&self) -> Option<&T> {
+        match &self.head {
+            Cons(elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        match &mut self.head {
+            Cons(elem, _) => Some(elem),
+            Nil => None,
+        }
     }
 
     // 计算链表长度
+    // 使用循环而不是递归，这样很长的链表也不会栈溢出
     fn len(&self) -> usize {
-        // 因为 Rust 会自动进行解引用操作 所以这里可以直接使用 self 而不是使用 *self
-        match self {
-            // 递归计算所有链表节点
-            Cons(_, next) => 1 + next.len(),
-            // 末尾节点没有值直接返回 0
-            Nil => 0,
-        }
-    }
-
-    // 格式化链表成字符串
-    fn stringify(&self) -> String {
-        // *号解引用会转移变量所有权，但是因为参数上声明是个引用
-        // 所以这里会报错，不能把值从引用中转移出来
-        // 解决方案就是在声明语句前加上 ref 关键字来表示变量是引用形式使用的
-
-        match *self {
-            // 这里使用 ref 关键字来声明变量是一个引用，而不是转移变量的所有权
-            // head 不需要添加 ref 关键字使用为 head 是一个 i32 的基础值，直接保存在栈上
-            // 而 Box<List> 的值是保存在堆上的。
-            Cons(head, ref tail) => {
-                format!("{}, {}", head, tail.stringify())
+        self.iter().count()
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self.head, Nil)
+    }
+
+    // 原地反转链表，只移动节点，不会重新分配内存
+    fn reverse(&mut self) {
+        let mut reversed = Nil;
+        let mut current = mem::replace(&mut self.head, Nil);
+        while let Cons(_, next) = &mut current {
+            // 把当前节点指向已经反转好的部分，然后当前节点就成了反转好的部分的第一个节点
+            let rest = mem::replace(&mut **next, reversed);
+            reversed = mem::replace(&mut current, rest);
+        }
+        self.head = reversed;
+    }
+
+    // 把另一个链表连接到当前链表的末尾
+    fn append(&mut self, mut other: List<T>) {
+        // 找到最后的 `Nil` 节点
+        let mut tail = &mut self.head;
+        while let Cons(_, next) = tail {
+            tail = next;
+        }
+        *tail = mem::replace(&mut other.head, Nil);
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        Iter { next: &self.head }
+    }
+
+    fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: Some(&mut self.head),
+        }
+    }
+}
+
+// 默认的 `Drop` 会先释放第一个节点，第一个节点再释放第二个节点……
+// 这样是递归的，链表太长的时候会栈溢出，所以这里使用循环逐个释放节点
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut current = mem::replace(&mut self.head, Nil);
+        while let Cons(_, next) = current {
+            // 把下一个节点移动出来以后，当前节点就不再指向任何节点了，释放它不会发生递归
+            current = *next;
+        }
+    }
+}
+
+// 借用链表中元素的迭代器
+struct Iter<'a, T> {
+    next: &'a Node<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.next {
+            Cons(elem, next) => {
+                self.next = next;
+                Some(elem)
             }
-            Nil => format!("Nil"),
+            Nil => None,
         }
     }
 }
 
+// 可变借用链表中元素的迭代器
+struct IterMut<'a, T> {
+    // 可变引用不能复制，所以使用 `Option` 把它 `take` 出来
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.next.take()? {
+            Cons(elem, next) => {
+                self.next = Some(next);
+                Some(elem)
+            }
+            Nil => None,
+        }
+    }
+}
+
+// 消耗掉链表的迭代器，每次从前面 `pop` 一个元素
+struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+// `collect` 得到的链表和迭代器的顺序一样
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        // 每次都添加到最前面得到的是反过来的顺序，最后再反转一次
+        let mut list = List::new();
+        for elem in iter {
+            list.push(elem);
+        }
+        list.reverse();
+        list
+    }
+}
+
+// 格式化链表成字符串，例如 `3, 2, 1, Nil`
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 使用迭代器依次输出每个元素，不会递归
+        for elem in self {
+            write!(f, "{}, ", elem)?;
+        }
+        write!(f, "Nil")
+    }
+}
+
 fn main() {
     // 创建一个空链表
     let mut list = List::new();
@@ -57,5 +237,119 @@ fn main() {
 
     // 查看链表的状态
     println!("linked list has length: {}", list.len());
-    println!("{}", list.stringify());
+    println!("{}", list);
+
+    // 查看和修改第一个元素
+    println!("peek: {:?}", list.peek());
+    if let Some(head) = list.peek_mut() {
+        *head *= 10;
+    }
+
+    // 所有元素加一
+    for elem in list.iter_mut() {
+        *elem += 1;
+    }
+    println!("after iter_mut: {}", list);
+
+    // 连接两个链表，然后反转
+    list.append((4..=6).collect());
+    println!("appended: {}", list);
+    list.reverse();
+    println!("reversed: {}", list);
+
+    // 从前面依次弹出元素
+    while let Some(elem) = list.pop() {
+        print!("{} ", elem);
+    }
+    println!("-> empty: {}", list.is_empty());
+
+    // 元素可以是任意类型
+    let words: List<&str> = "the quick brown fox".split(' ').collect();
+    let lengths: Vec<usize> = words.into_iter().map(str::len).collect();
+    println!("word lengths: {:?}", lengths);
+
+    // 很长的链表也不会栈溢出
+    let long: List<u32> = (0..1_000_000).collect();
+    println!("long list has length: {}", long.len());
+
+    // `RcList` 的 `prepend` 不会消耗掉原来的链表，新的链表和原来的链表共享尾部
+    let tail: RcList<u32> = (1..=3).collect();
+    let a = tail.prepend(0);
+    let b = tail.prepend(9);
+    println!("a: {}", a);
+    println!("b: {}", b);
+    println!("tail is still usable: {}", tail);
+    println!("a and b share the tail: {}", a.tail().ptr_eq(&b.tail()));
+    println!("head of b: {:?}, tail of b: {:?}", b.head(), b.tail());
+
+    // 结构相等只比较元素，不要求共享节点
+    let copy: RcList<u32> = (0..=3).collect();
+    println!("a == copy: {}, shared: {}", a == copy, a.ptr_eq(&copy));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONG: u64 = 1_000_000;
+
+    #[test]
+    fn push_pop_peek() {
+        let mut list = List::new().prepend(1).prepend(2);
+        assert_eq!(list.peek(), Some(&2));
+        list.push(3);
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.peek(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn display_and_collect_keep_order() {
+        let list: List<i32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(list.to_string(), "1, 2, 3, Nil");
+        assert_eq!(List::<i32>::new().to_string(), "Nil");
+    }
+
+    #[test]
+    fn reverse_and_append() {
+        let mut list: List<i32> = (1..=3).collect();
+        list.reverse();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+
+        list.append((7..=8).collect());
+        list.append(List::new());
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [3, 2, 1, 7, 8]);
+
+        let mut empty = List::new();
+        empty.append((1..=2).collect());
+        assert_eq!(empty.to_string(), "1, 2, Nil");
+    }
+
+    #[test]
+    fn long_list_len_display_and_drop() {
+        let list: List<u64> = (0..LONG).collect();
+        assert_eq!(list.len(), LONG as usize);
+        assert!(list.to_string().ends_with("999998, 999999, Nil"));
+        drop(list);
+    }
+
+    #[test]
+    fn long_list_iterators() {
+        let mut list: List<u64> = (0..LONG).collect();
+        for elem in &mut list {
+            *elem *= 2;
+        }
+        assert_eq!(list.iter().sum::<u64>(), LONG * (LONG - 1));
+
+        list.reverse();
+        assert_eq!(list.peek(), Some(&(2 * (LONG - 1))));
+
+        let mut other: List<u64> = (0..LONG).collect();
+        other.append(list);
+        assert_eq!(other.len(), 2 * LONG as usize);
+        assert_eq!(other.into_iter().count(), 2 * LONG as usize);
+    }
 }
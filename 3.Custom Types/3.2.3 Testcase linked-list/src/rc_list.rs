@@ -0,0 +1,239 @@
+//!
+//! 使用 `Rc` 共享尾部的不可变链表
+//!
+//! `main.rs` 中的 `List` 使用 `Box` 保存下一个节点，每个节点只有一个所有者，所以 `prepend` 需要消耗掉原来的链表。
+//! 这里的 `RcList` 使用引用计数指针 `Rc` 保存下一个节点（参考第 19 章的 `rc_` 示例），
+//! 多个链表可以共享同一个尾部，`prepend` 只需要借用原来的链表，原来的链表仍然可以继续使用。
+//!
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+pub struct RcList<T> {
+    head: Option<Rc<Node<T>>>,
+}
+
+struct Node<T> {
+    elem: T,
+    next: RcList<T>,
+}
+
+impl<T> RcList<T> {
+    pub fn new() -> Self {
+        RcList { head: None }
+    }
+
+    /// 返回一个在最前面添加了 `elem` 的新链表，新链表的尾部就是当前链表，两者共享所有的节点
+    pub fn prepend(&self, elem: T) -> RcList<T> {
+        RcList {
+            head: Some(Rc::new(Node {
+                elem,
+                next: self.clone(),
+            })),
+        }
+    }
+
+    /// 第一个元素
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// 去掉第一个元素以后剩下的链表，空链表的尾部还是空链表
+    ///
+    /// 只是复制了一个 `Rc` 指针，不会复制任何元素。
+    pub fn tail(&self) -> RcList<T> {
+        match &self.head {
+            Some(node) => node.next.clone(),
+            None => RcList::new(),
+        }
+    }
+
+    // 使用循环计算长度，不会递归
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// 两个链表是否是同一组节点，和 `Rc::ptr_eq` 一样只比较指针
+    pub fn ptr_eq(&self, other: &RcList<T>) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T> Default for RcList<T> {
+    fn default() -> Self {
+        RcList::new()
+    }
+}
+
+// 复制链表只是复制第一个节点的 `Rc` 指针，引用计数 `+1`，所以不需要 `T: Clone`
+impl<T> Clone for RcList<T> {
+    fn clone(&self) -> Self {
+        RcList {
+            head: self.head.clone(),
+        }
+    }
+}
+
+// 和 `Box` 的版本一样，默认的 `Drop` 是递归的
+// 这里逐个检查节点，只有当前链表是节点唯一的所有者的时候才释放这个节点并继续处理下一个节点，
+// 遇到和其它链表共享的节点就停下来，剩下的部分由其它链表负责释放
+impl<T> Drop for RcList<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => head = node.next.head.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.head.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RcList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// 节点创建以后就不能修改了，所以只能先把元素收集起来，再从后往前依次添加
+impl<T> FromIterator<T> for RcList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elems: Vec<T> = iter.into_iter().collect();
+        let mut list = RcList::new();
+        for elem in elems.into_iter().rev() {
+            list = list.prepend(elem);
+        }
+        list
+    }
+}
+
+// 结构相等：元素依次相等就相等，不要求是同一组节点
+impl<T: PartialEq> PartialEq for RcList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        loop {
+            // 遇到共享的节点，剩下的部分一定相等，不需要再比较
+            if a.ptr_eq(&b) {
+                return true;
+            }
+            match (a.head(), b.head()) {
+                (Some(x), Some(y)) if x == y => {}
+                _ => return false,
+            }
+            a = a.tail();
+            b = b.tail();
+        }
+    }
+}
+
+impl<T: Eq> Eq for RcList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for RcList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+// 和 `List` 的输出格式一样，例如 `3, 2, 1, Nil`
+impl<T: fmt::Display> fmt::Display for RcList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for elem in self {
+            write!(f, "{}, ", elem)?;
+        }
+        write!(f, "Nil")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepend_shares_tail() {
+        let tail: RcList<i32> = (1..=3).collect();
+        let a = tail.prepend(0);
+        let b = tail.prepend(9);
+
+        assert_eq!(a.to_string(), "0, 1, 2, 3, Nil");
+        assert_eq!(b.head(), Some(&9));
+        assert!(a.tail().ptr_eq(&tail));
+        assert!(b.tail().ptr_eq(&tail));
+
+        // 原来的链表仍然可以使用
+        assert_eq!(tail.len(), 3);
+        drop(a);
+        drop(b);
+        assert_eq!(tail.to_string(), "1, 2, 3, Nil");
+    }
+
+    #[test]
+    fn head_and_tail_of_empty() {
+        let empty = RcList::<i32>::new();
+        assert_eq!(empty.head(), None);
+        assert!(empty.tail().is_empty());
+        assert_eq!(empty.to_string(), "Nil");
+    }
+
+    #[test]
+    fn structural_equality() {
+        let a: RcList<i32> = (1..=3).collect();
+        let b: RcList<i32> = (1..=3).collect();
+        assert!(!a.ptr_eq(&b));
+        assert_eq!(a, b);
+        assert_eq!(a.prepend(0), b.prepend(0));
+        assert_ne!(a, b.tail());
+        assert_ne!(a, b.prepend(0));
+        assert_eq!(format!("{:?}", a), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn long_shared_chain_drop() {
+        let long: RcList<u64> = (0..1_000_000).collect();
+        let mut shared = long.prepend(1);
+        assert_eq!(shared.len(), 1_000_001);
+        assert_eq!(shared, long.prepend(1));
+
+        // `shared` 的尾部就是 `long`，释放 `long` 只会减少引用计数，不会释放任何节点
+        drop(long);
+        assert_eq!(shared.iter().sum::<u64>(), 499_999_500_001);
+
+        // 这时 `shared` 是所有节点唯一的所有者，换成尾部的时候只会释放原来的第一个节点
+        shared = shared.tail();
+        assert_eq!(shared.len(), 1_000_000);
+        // 释放剩下的一百万个节点，不会栈溢出
+        drop(shared);
+    }
+}
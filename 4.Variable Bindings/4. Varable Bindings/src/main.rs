@@ -1,3 +1,6 @@
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(unused_variables)]
+
 fn main() {
     let an_integer = 1u32;
     let a_boolean = true;
@@ -0,0 +1,346 @@
+use std::fmt;
+
+/// 转换过程中使用的中间值，可以无损的表示所有的基础数字类型
+///
+/// 能放进 `i128` 的整数都使用 `Int` 表示，只有超过 `i128::MAX` 的 `u128` 才使用 `UInt`，
+/// 这样同一个整数只有一种表示方式。`f32` 转换成 `f64` 也是无损的。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Int(i128),
+    UInt(u128),
+    Float(f64),
+}
+
+// 2 的 127 次方和 128 次方，`f64` 可以精确的表示
+const TWO_127: f64 = 170141183460469231731687303715884105728.0;
+const TWO_128: f64 = 340282366920938463463374607431768211456.0;
+
+impl Value {
+    /// 两个值表示的是否是同一个数字，两个 `NaN` 也当做是相同的
+    fn same(self, other: Value) -> bool {
+        match (self, other) {
+            (Value::Float(a), Value::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            // 浮点数转换成整数的时候是饱和转换，所以需要先检查范围，
+            // 否则 `2^127 as i128` 会得到 `i128::MAX`，看起来和 `i128::MAX` 相同
+            (Value::Float(f), Value::Int(i)) | (Value::Int(i), Value::Float(f)) => {
+                f.fract() == 0.0 && (-TWO_127..TWO_127).contains(&f) && f as i128 == i
+            }
+            (Value::Float(f), Value::UInt(u)) | (Value::UInt(u), Value::Float(f)) => {
+                f.fract() == 0.0 && (0.0..TWO_128).contains(&f) && f as u128 == u
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    fn is_infinite(self) -> bool {
+        matches!(self, Value::Float(f) if f.is_infinite())
+    }
+}
+
+/// 所有的基础数字类型，`i8` 到 `i128`、`u8` 到 `u128`、`isize`、`usize`、`f32` 和 `f64`
+pub trait Primitive: Copy + fmt::Debug + fmt::Display {
+    /// 类型的名字，例如 `u8`
+    const NAME: &'static str;
+    const BITS: u32;
+    const IS_FLOAT: bool;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn to_value(self) -> Value;
+
+    /// 和 `as` 关键字的转换规则完全相同
+    fn from_value(value: Value) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ident),*) => {
+        $(impl Primitive for $t {
+            const NAME: &'static str = stringify!($t);
+            const BITS: u32 = $t::BITS;
+            const IS_FLOAT: bool = false;
+            const MIN: Self = $t::MIN;
+            const MAX: Self = $t::MAX;
+
+            fn to_value(self) -> Value {
+                // 只有超过 `i128::MAX` 的 `u128` 放不进 `i128`
+                match i128::try_from(self) {
+                    Ok(i) => Value::Int(i),
+                    Err(_) => Value::UInt(self as u128),
+                }
+            }
+
+            fn from_value(value: Value) -> Self {
+                // 中间值的范围比原始类型大，但是截断低位以后的结果和直接转换是一样的
+                match value {
+                    Value::Int(i) => i as $t,
+                    Value::UInt(u) => u as $t,
+                    Value::Float(f) => f as $t,
+                }
+            }
+        })*
+    };
+}
+
+macro_rules! impl_float {
+    ($($t:ident: $bits:literal),*) => {
+        $(impl Primitive for $t {
+            const NAME: &'static str = stringify!($t);
+            const BITS: u32 = $bits;
+            const IS_FLOAT: bool = true;
+            const MIN: Self = $t::MIN;
+            const MAX: Self = $t::MAX;
+
+            fn to_value(self) -> Value {
+                Value::Float(self as f64)
+            }
+
+            fn from_value(value: Value) -> Self {
+                match value {
+                    Value::Int(i) => i as $t,
+                    Value::UInt(u) => u as $t,
+                    Value::Float(f) => f as $t,
+                }
+            }
+        })*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_float!(f32: 32, f64: 64);
+
+/// 和 `as` 一样的转换，整数超出范围的时候只保留低位，浮点数转换成整数的时候是饱和转换
+pub fn wrapping_cast<T: Primitive>(value: impl Primitive) -> T {
+    T::from_value(value.to_value())
+}
+
+/// 只有在目标类型可以精确表示原来的数字的时候才转换成功
+///
+/// 整数超出范围、浮点数带有小数、整数转换成浮点数的时候丢失精度等情况都会返回 `None`，
+/// `NaN` 只能转换成浮点数的 `NaN`。
+pub fn checked_cast<T: Primitive>(value: impl Primitive) -> Option<T> {
+    let value = value.to_value();
+    let output = T::from_value(value);
+    if output.to_value().same(value) {
+        Some(output)
+    } else {
+        None
+    }
+}
+
+/// 超出范围的时候转换成目标类型的最大值或者最小值
+///
+/// 浮点数转换成整数的时候小数部分会被去掉，`NaN` 转换成 0，这和 `as` 的规则相同；
+/// 整数转换成浮点数的时候取最接近的值，超出浮点数的范围的时候是最大的有限值，而不是无穷大。
+pub fn saturating_cast<T: Primitive>(value: impl Primitive) -> T {
+    let value = value.to_value();
+    let output = T::from_value(value);
+
+    if T::IS_FLOAT {
+        // 原来的数字不是无穷大，转换以后却变成了无穷大
+        if output.to_value().is_infinite() && !value.is_infinite() {
+            return if is_negative(value) { T::MIN } else { T::MAX };
+        }
+        return output;
+    }
+
+    match value {
+        // 浮点数转换成整数本来就是饱和转换
+        Value::Float(_) => output,
+        _ if greater(value, T::MAX.to_value()) => T::MAX,
+        _ if greater(T::MIN.to_value(), value) => T::MIN,
+        _ => output,
+    }
+}
+
+fn is_negative(value: Value) -> bool {
+    match value {
+        Value::Int(i) => i < 0,
+        Value::UInt(_) => false,
+        Value::Float(f) => f < 0.0,
+    }
+}
+
+// 比较两个整数，`UInt` 总是比 `Int` 大
+fn greater(a: Value, b: Value) -> bool {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => a > b,
+        (Value::UInt(a), Value::UInt(b)) => a > b,
+        (Value::UInt(_), Value::Int(_)) => true,
+        _ => false,
+    }
+}
+
+/// `as` 转换的时候使用的规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// 目标类型可以精确表示原来的数字
+    Exact,
+    /// 整数超出范围，只保留低位
+    Truncated,
+    /// 浮点数去掉了小数部分，向 0 取整
+    TowardZero,
+    /// 浮点数超出了整数的范围，转换成最大值或者最小值
+    Saturated,
+    /// `NaN` 转换成整数的结果是 0
+    NanToZero,
+    /// 转换成浮点数的时候丢失了精度，取最接近的值
+    Rounded,
+    /// 超出了浮点数的范围，转换成无穷大
+    Infinity,
+}
+
+/// 一次 `as` 转换的结果，以及转换时使用的规则
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CastReport<S, T> {
+    pub input: S,
+    pub output: T,
+    pub rule: Rule,
+}
+
+/// 使用 `as` 的规则转换，并说明使用了哪条规则
+pub fn report<S: Primitive, T: Primitive>(input: S) -> CastReport<S, T> {
+    let value = input.to_value();
+    let output = T::from_value(value);
+
+    let rule = if output.to_value().same(value) {
+        Rule::Exact
+    } else if T::IS_FLOAT {
+        if output.to_value().is_infinite() {
+            Rule::Infinity
+        } else {
+            Rule::Rounded
+        }
+    } else {
+        match value {
+            Value::Float(f) if f.is_nan() => Rule::NanToZero,
+            // 去掉小数部分以后能精确表示，说明只是丢掉了小数部分，否则就是超出了范围
+            Value::Float(f) if output.to_value().same(Value::Float(f.trunc())) => Rule::TowardZero,
+            Value::Float(_) => Rule::Saturated,
+            _ => Rule::Truncated,
+        }
+    };
+
+    CastReport {
+        input,
+        output,
+        rule,
+    }
+}
+
+impl<S: Primitive, T: Primitive> fmt::Display for CastReport<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{} as {} = {}: ",
+            self.input,
+            S::NAME,
+            T::NAME,
+            self.output
+        )?;
+        match self.rule {
+            Rule::Exact => f.write_str("exact"),
+            Rule::Truncated => write!(f, "truncated to the low {} bits", T::BITS),
+            Rule::TowardZero => f.write_str("fraction dropped, rounded toward zero"),
+            Rule::Saturated => write!(f, "saturated to the {} range", T::NAME),
+            Rule::NanToZero => f.write_str("NaN becomes 0"),
+            Rule::Rounded => f.write_str("rounded to the nearest representable value"),
+            Rule::Infinity => write!(f, "out of {} range, became infinity", T::NAME),
+        }
+    }
+}
+
+/// 在调用 `to_int_unchecked` 之前先检查它的前提条件
+pub trait ToIntValidated<T> {
+    /// 不是 `NaN`、不是无穷大、并且去掉小数部分以后目标类型可以表示的时候才转换，否则返回 `None`
+    fn to_int_validated(self) -> Option<T>;
+}
+
+macro_rules! impl_to_int_validated {
+    ($float:ident => $($int:ident),*) => {
+        $(impl ToIntValidated<$int> for $float {
+            fn to_int_validated(self) -> Option<$int> {
+                // 去掉小数部分以后可以精确的转换，说明原来的数字是有限的，并且在目标类型的范围内
+                checked_cast::<$int>(self.trunc())?;
+                // SAFETY: 上面已经证明了 `self` 不是 `NaN`、不是无穷大，
+                // 并且去掉小数部分以后可以用目标类型表示，这正是 `to_int_unchecked` 要求的前提条件
+                Some(unsafe { self.to_int_unchecked::<$int>() })
+            }
+        })*
+    };
+}
+
+impl_to_int_validated!(f32 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_to_int_validated!(f64 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapping_matches_as() {
+        assert_eq!(wrapping_cast::<u8>(1000), 1000_i32 as u8);
+        assert_eq!(wrapping_cast::<i8>(232u8), 232u8 as i8);
+        assert_eq!(wrapping_cast::<u8>(-1i8), 255);
+        assert_eq!(wrapping_cast::<u8>(300.0f32), 255);
+        assert_eq!(wrapping_cast::<u8>(f32::NAN), 0);
+        assert_eq!(wrapping_cast::<i64>(u128::MAX), -1);
+        assert_eq!(wrapping_cast::<f32>(u128::MAX), f32::INFINITY);
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(checked_cast::<u8>(255i32), Some(255));
+        assert_eq!(checked_cast::<u8>(256i32), None);
+        assert_eq!(checked_cast::<u8>(-1i32), None);
+        assert_eq!(checked_cast::<i128>(u128::MAX), None);
+        assert_eq!(checked_cast::<u128>(u128::MAX), Some(u128::MAX));
+        assert_eq!(checked_cast::<u8>(65.0f32), Some(65));
+        assert_eq!(checked_cast::<u8>(65.4321f32), None);
+        assert_eq!(checked_cast::<u8>(f32::NAN), None);
+        assert_eq!(checked_cast::<i128>(TWO_127), None);
+        assert_eq!(checked_cast::<f32>(16_777_216i32), Some(16_777_216.0));
+        assert_eq!(checked_cast::<f32>(16_777_217i32), None);
+        assert_eq!(checked_cast::<f32>(0.1f64), None);
+        assert_eq!(checked_cast::<f64>(0.1f32), Some(0.1f32 as f64));
+        assert!(checked_cast::<f32>(f64::NAN).unwrap().is_nan());
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(saturating_cast::<u8>(1000), 255u8);
+        assert_eq!(saturating_cast::<u8>(-1000), 0u8);
+        assert_eq!(saturating_cast::<i8>(u128::MAX), i8::MAX);
+        assert_eq!(saturating_cast::<u64>(i128::MIN), 0);
+        assert_eq!(saturating_cast::<i32>(-100.7f64), -100);
+        assert_eq!(saturating_cast::<u8>(f32::NAN), 0);
+        assert_eq!(saturating_cast::<f32>(u128::MAX), f32::MAX);
+        assert_eq!(saturating_cast::<f32>(-1e300f64), f32::MIN);
+        assert_eq!(saturating_cast::<f32>(f64::INFINITY), f32::INFINITY);
+    }
+
+    #[test]
+    fn report_rules() {
+        assert_eq!(report::<i32, u8>(100).rule, Rule::Exact);
+        assert_eq!(report::<i32, u8>(1000).rule, Rule::Truncated);
+        assert_eq!(report::<f32, u8>(65.4321).rule, Rule::TowardZero);
+        assert_eq!(report::<f32, u8>(-100.0).rule, Rule::Saturated);
+        assert_eq!(report::<f32, u8>(f32::INFINITY).rule, Rule::Saturated);
+        assert_eq!(report::<f32, u8>(f32::NAN).rule, Rule::NanToZero);
+        assert_eq!(report::<i32, f32>(16_777_217).rule, Rule::Rounded);
+        assert_eq!(report::<f64, f32>(1e300).rule, Rule::Infinity);
+        assert_eq!(
+            report::<i32, u8>(1000).to_string(),
+            "1000i32 as u8 = 232: truncated to the low 8 bits"
+        );
+    }
+
+    #[test]
+    fn validated_unchecked() {
+        assert_eq!(ToIntValidated::<u8>::to_int_validated(255.9f32), Some(255));
+        assert_eq!(ToIntValidated::<u8>::to_int_validated(-0.5f32), Some(0));
+        assert_eq!(ToIntValidated::<u8>::to_int_validated(300.0f32), None);
+        assert_eq!(ToIntValidated::<u8>::to_int_validated(-100.0f32), None);
+        assert_eq!(ToIntValidated::<u8>::to_int_validated(f32::NAN), None);
+        assert_eq!(ToIntValidated::<i64>::to_int_validated(f64::INFINITY), None);
+    }
+}
@@ -7,21 +7,28 @@
 //!
 // Suppress all warnings from casts which overflow.
 #![allow(overflowing_literals)]
+// 下面故意演示了 `NaN` 转换成整数的结果，以及 `1000 as u16` 这样的转换
+#![allow(clippy::cast_nan_to_int, clippy::unnecessary_cast)]
+
+// 检查转换结果的工具函数放在了单独的 `cast.rs` 文件中
+mod cast;
+
+use cast::{CastReport, ToIntValidated};
 
 fn main() {
     let decimal = 65.4321_f32;
 
     // 错误! 没有从浮点数到整数的隐式转换规则。
-    let integer: u8 = decimal;
-    // FIXME ^ 注释这行代码
+    // let integer: u8 = decimal;
+    // TODO ^ 移除注释查看错误
 
     // 手动明确指定转换规则
     let integer = decimal as u8; // f32 强制转换到 u8
     let character = integer as char; // u8 强制转换到 char
 
     // 错误! 有几个转换限制，f32 不能直接转换为 char
-    let character = decimal as char;
-    // FIXME ^ 注释这行代码
+    // let character = decimal as char;
+    // TODO ^ 移除注释查看错误
 
     println!("Casting: {} -> {} -> {}", decimal, integer, character);
 
@@ -72,17 +79,35 @@ fn main() {
     // nan 转换为 u8 是 0
     println!("   nan as u8 is : {}", f32::NAN as u8);
 
-    // 上面这些转换会有一些运行时的性能损失，可以通过使用不安全的方法来避免，
-    // 但是这些方法可能会溢出并返回不安全的值，应该小心使用这些方法。
-    unsafe {
-        // 300.0 转换为 u8 是 44
-        println!(" 300.0 as u8 is : {}", 300.0_f32.to_int_unchecked::<u8>());
-        // -100.0 转换为 u8 是 156
-        println!(
-            "-100.0 as u8 is : {}",
-            (-100.0_f32).to_int_unchecked::<u8>()
-        );
-        // nan 转换为 u8 是 0
-        println!("   nan as u8 is : {}", f32::NAN.to_int_unchecked::<u8>());
+    // 上面这些转换会有一些运行时的性能损失，可以通过使用不安全的方法 `to_int_unchecked` 来避免，
+    // 但是这个方法要求浮点数是有限的，并且去掉小数部分以后能放进目标类型，否则是未定义行为（UB），
+    // 例如 `300.0_f32.to_int_unchecked::<u8>()` 的结果是不确定的。
+    // `to_int_validated` 先检查这些前提条件，满足的时候才调用 `to_int_unchecked`。
+    for value in [65.4321_f32, 300.0, -100.0, f32::NAN] {
+        let result: Option<u8> = value.to_int_validated();
+        println!("{:>7} to u8 unchecked is : {:?}", value, result);
+    }
+
+    // `as` 转换不会报错，`checked_cast` 只在可以精确表示的时候才成功，`saturating_cast` 会使用边界值
+    println!("checked 1000 as u8: {:?}", cast::checked_cast::<u8>(1000));
+    println!("checked  232 as u8: {:?}", cast::checked_cast::<u8>(232));
+    println!(
+        "saturating 1000 as u8: {}",
+        cast::saturating_cast::<u8>(1000)
+    );
+    println!("saturating -1 as u8: {}", cast::saturating_cast::<u8>(-1i8));
+    println!("wrapping 1000 as u8: {}", cast::wrapping_cast::<u8>(1000));
+
+    // 解释上面每一次 `as` 转换使用的规则
+    let reports: [CastReport<i32, u8>; 2] = [cast::report(1000), cast::report(232)];
+    for report in reports {
+        println!("{}", report);
     }
+    println!("{}", cast::report::<i32, i8>(232));
+    println!("{}", cast::report::<f32, u8>(decimal));
+    println!("{}", cast::report::<f32, u8>(300.0));
+    println!("{}", cast::report::<f32, u8>(-100.0));
+    println!("{}", cast::report::<f32, u8>(f32::NAN));
+    println!("{}", cast::report::<i32, f32>(16_777_217));
+    println!("{}", cast::report::<f64, f32>(1e300));
 }
@@ -3,6 +3,9 @@
 //! 所以很多时候不需要手动声明类型，让推断引擎自动推断出类型
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::vec_init_then_push)]
+
 fn main() {
     // 手动声明类型
     let elem = 5u8;
@@ -8,6 +8,11 @@
 //! 比如 std::io::Result<T> 就是 std::io::Result<T, std::io:Error> 的类型别名
 //!
 
+// 带有单位的数量类型放在了单独的 `quantity.rs` 文件中
+mod quantity;
+
+use quantity::{Gram, Kilogram, Millimeter, Millisecond, Nanosecond, Quantity, Second};
+
 // `NanoSecond`, `Inch`, 和 `U64` 都是 `u64` 的类型别名
 type NanoSecond = u64;
 type Inch = u64;
@@ -26,4 +31,42 @@ fn main() {
         inches,
         nanoseconds + inches
     );
+
+    // 要得到真正不同的类型，需要使用新类型（newtype），这里使用幽灵类型来标记单位，
+    // 类型别名仍然可以用来简化名字。
+    type Nanoseconds = Quantity<Nanosecond>;
+    type Inches = Quantity<quantity::Inch>;
+
+    let nanoseconds = Nanoseconds::new(5.0);
+    let inches = Inches::new(2.0);
+    println!(
+        "{} + {} = {}",
+        nanoseconds,
+        nanoseconds,
+        nanoseconds + nanoseconds
+    );
+    println!("{} + {} = {}", inches, inches, inches + inches);
+
+    // 不同量纲的数量不能相加
+    // let unit = nanoseconds + inches;
+    // TODO ^ 移除上面一行代码的注释看看编译错误
+
+    // 同一个量纲的单位之间需要明确的转换
+    println!("{} = {}", inches, inches.convert::<Millimeter>());
+    let elapsed = Quantity::<Second>::new(1.5);
+    println!("{} = {}", elapsed, elapsed.convert::<Millisecond>());
+    println!(
+        "{} + {} = {}",
+        elapsed,
+        nanoseconds,
+        elapsed.convert() + nanoseconds
+    );
+    let flour = Quantity::<Gram>::new(750.0) + Quantity::<Kilogram>::new(1.5).convert();
+    // `value` 去掉单位，只留下数字
+    let kilograms: f64 = flour.convert::<Kilogram>().value();
+    println!("{} = {}kg", flour, kilograms);
+
+    // 不同量纲之间不能转换
+    // let wrong = inches.convert::<Nanosecond>();
+    // TODO ^ 移除上面一行代码的注释看看编译错误
 }
@@ -0,0 +1,221 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Sub};
+
+// 量纲只用来区分类型，和第 14.9 章一样使用没有枚举项的枚举，它们不能被创建出来
+#[derive(Debug)]
+pub enum Time {}
+#[derive(Debug)]
+pub enum Length {}
+#[derive(Debug)]
+pub enum Mass {}
+
+/// 计量单位
+///
+/// 同一个量纲的单位之间可以互相转换，`SCALE` 是一个单位等于多少个基本单位，
+/// 时间的基本单位是纳秒，长度是微米，质量是毫克。
+///
+/// 和第 14.9 章的 `length.rs` 一样，所有单位都定义成基本单位的整数倍，
+/// 因为像 25.4 这样的小数不能用浮点数精确表示，换算的时候使用约分以后的整数比例。
+pub trait Unit {
+    /// 单位所属的量纲，例如 `Time`
+    type Dimension;
+    /// 输出的时候跟在数字后面的符号，例如 `ns`
+    const SYMBOL: &'static str;
+    const SCALE: u64;
+}
+
+macro_rules! units {
+    ($($(#[$meta:meta])* $name:ident: $dimension:ty = $scale:literal $symbol:literal;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug)]
+            pub enum $name {}
+
+            impl Unit for $name {
+                type Dimension = $dimension;
+                const SYMBOL: &'static str = $symbol;
+                const SCALE: u64 = $scale;
+            }
+        )*
+    };
+}
+
+units! {
+    Nanosecond: Time = 1 "ns";
+    Millisecond: Time = 1_000_000 "ms";
+    Second: Time = 1_000_000_000 "s";
+    Millimeter: Length = 1_000 "mm";
+    /// 1 英寸正好是 25.4 毫米
+    Inch: Length = 25_400 "in";
+    Gram: Mass = 1_000 "g";
+    Kilogram: Mass = 1_000_000 "kg";
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// 带有单位的数量
+///
+/// 单位 `U` 是幽灵类型，不占用空间，只有单位相同的数量才能相加或者比较，
+/// 例如 `Quantity<Nanosecond>` 和 `Quantity<Inch>` 相加会导致编译错误。
+/// 不同的单位之间需要使用 [`Quantity::convert`] 明确的转换。
+pub struct Quantity<U: Unit> {
+    value: f64,
+    unit: PhantomData<U>,
+}
+
+impl<U: Unit> Quantity<U> {
+    pub fn new(value: f64) -> Quantity<U> {
+        Quantity {
+            value,
+            unit: PhantomData,
+        }
+    }
+
+    /// 不带单位的数字
+    pub fn value(self) -> f64 {
+        self.value
+    }
+
+    /// 转换成同一个量纲的其他单位，量纲不同的时候会导致编译错误
+    pub fn convert<V: Unit<Dimension = U::Dimension>>(self) -> Quantity<V> {
+        // 约分以后先乘以分子再除以分母，结果和精确值相比最多只有一次舍入
+        let divisor = gcd(U::SCALE, V::SCALE);
+        let numerator = (U::SCALE / divisor) as f64;
+        let denominator = (V::SCALE / divisor) as f64;
+        Quantity::new(self.value * numerator / denominator)
+    }
+}
+
+// 单位只是一个标记，和第 14.9 章的 `Length` 一样手动实现这些特性，原因见 `length.rs`
+impl<U: Unit> Clone for Quantity<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U: Unit> Copy for Quantity<U> {}
+
+impl<U: Unit> PartialEq for Quantity<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<U: Unit> PartialOrd for Quantity<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<U: Unit> fmt::Debug for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quantity({:?}{})", self.value, U::SYMBOL)
+    }
+}
+
+// 数字后面紧跟着单位的符号，例如 `5ns`，精度之类的格式设置作用在数字上
+impl<U: Unit> fmt::Display for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)?;
+        f.write_str(U::SYMBOL)
+    }
+}
+
+// 只有单位完全相同的数量才能相加减
+impl<U: Unit> Add for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn add(self, rhs: Quantity<U>) -> Quantity<U> {
+        Quantity::new(self.value + rhs.value)
+    }
+}
+
+impl<U: Unit> Sub for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn sub(self, rhs: Quantity<U>) -> Quantity<U> {
+        Quantity::new(self.value - rhs.value)
+    }
+}
+
+// 乘以或者除以一个没有单位的数字，单位不变
+impl<U: Unit> Mul<f64> for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn mul(self, rhs: f64) -> Quantity<U> {
+        Quantity::new(self.value * rhs)
+    }
+}
+
+impl<U: Unit> Div<f64> for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn div(self, rhs: f64) -> Quantity<U> {
+        Quantity::new(self.value / rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_suffix() {
+        assert_eq!(Quantity::<Nanosecond>::new(5.0).to_string(), "5ns");
+        assert_eq!(Quantity::<Inch>::new(2.5).to_string(), "2.5in");
+        assert_eq!(
+            format!("{:.2}", Quantity::<Kilogram>::new(1.0 / 3.0)),
+            "0.33kg"
+        );
+    }
+
+    #[test]
+    fn convert_time() {
+        let time = Quantity::<Second>::new(1.5);
+        assert_eq!(time.convert::<Millisecond>(), Quantity::new(1500.0));
+        assert_eq!(time.convert::<Nanosecond>(), Quantity::new(1.5e9));
+        assert_eq!(
+            Quantity::<Nanosecond>::new(2_500_000.0).convert::<Millisecond>(),
+            Quantity::new(2.5)
+        );
+    }
+
+    #[test]
+    fn convert_length_round_trip() {
+        let inches = Quantity::<Inch>::new(10.0);
+        let mm = inches.convert::<Millimeter>();
+        assert_eq!(mm, Quantity::new(254.0));
+        assert_eq!(mm.convert::<Inch>(), inches);
+        assert_eq!(
+            Quantity::<Gram>::new(250.0).convert::<Kilogram>().value(),
+            0.25
+        );
+        // 12 英寸正好是 304.8 毫米，直接乘以 25.4 会得到 304.79999999999995
+        assert_eq!(
+            Quantity::<Inch>::new(12.0).convert::<Millimeter>(),
+            Quantity::new(304.8)
+        );
+    }
+
+    #[test]
+    fn arithmetic_keeps_unit() {
+        let a = Quantity::<Millisecond>::new(3.0);
+        let b = Quantity::<Millisecond>::new(2.0);
+        assert_eq!(a + b, Quantity::new(5.0));
+        assert_eq!(a - b, Quantity::new(1.0));
+        assert_eq!(a * 2.0, Quantity::new(6.0));
+        assert_eq!(a / 2.0, Quantity::new(1.5));
+        assert!(a > b);
+        // 转换成相同的单位以后才能相加
+        let total = a + Quantity::<Second>::new(1.0).convert();
+        assert_eq!(total.to_string(), "1003ms");
+    }
+}
@@ -5,6 +5,12 @@
 //! 比如 A类型可以通过B类型转换过来的话，那么也应该可以从A类型转换到B类型
 //!
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(dead_code, unused_variables)]
+
+// 可以保存任意整数类型的 `Number` 放在了单独的 `number.rs` 文件中
+mod number;
+
 /// From 特性(trait) 允许我们使用一个明确的其他类型值来实例化一个当前的类型
 /// 标准库提供的很多类型都实现了该特性，用于转换原始类型和公共类型
 ///
@@ -47,10 +53,11 @@ fn example_into() {
         value: i32,
     }
 
-    // 如果指定的类型已经实现了 From 特性的话 就不用手动再次实现 Into 特性了
-    impl Into<Number> for i32 {
-        fn into(self) -> Number {
-            Number { value: self }
+    // 如果指定的类型已经实现了 From 特性的话 就不用手动再次实现 Into 特性了，
+    // 标准库为所有实现了 `From<T> for U` 的类型自动实现了 `Into<U> for T`
+    impl From<i32> for Number {
+        fn from(value: i32) -> Self {
+            Number { value }
         }
     }
 
@@ -58,13 +65,46 @@ fn example_into() {
 
     // 手动指定类型进行转换
     let num: Number = int.into();
-    // 通过 Trait 对象，明确给出目标类型，调用转换方法
+    // 通过 Trait 对象，明确给出目标类型，调用转换方法，实际调用的是上面的 `from`
     let num = Into::<Number>::into(int);
 
     println!("My number is {:?}", num);
 }
 
+/// 标准库只为不会丢失数据的整数转换实现了 `From`，例如 `u8` 到 `i32`，
+/// 可能超出范围的转换需要使用 `TryFrom`，泛型的 `Number<T>` 也遵循同样的规则
+fn example_number() {
+    use number::Number;
+
+    // 不会丢失数据的转换
+    let small: Number<i64> = 200_u8.into();
+    let wide = Number::<i128>::from(i64::MIN);
+    println!("{:?} {}", small, wide);
+
+    // 编译错误：`i32` 转换成 `u8` 可能会丢失数据，所以没有实现 `From`
+    // let number: Number<u8> = 300_i32.into();
+    // TODO ^ 移除上面一行代码的注释看看编译错误
+
+    // 可能超出范围的转换只能使用 `TryFrom`
+    for value in [200_i32, 300, -1] {
+        match Number::<u8>::try_from(value) {
+            Ok(number) => println!("ok: {}", number),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+
+    // 转换回整数，可以是原来的类型，也可以是更大的类型
+    let number = Number::new(-7_i16);
+    let value: i16 = number.into();
+    println!("{} {} {}", value, i64::from(number), f64::from(value));
+}
+
+rbe::sections! {
+    example_from: "`From` 特性",
+    example_into: "`Into` 特性",
+    example_number: "泛型的 `Number<T>`",
+}
+
 fn main() {
-    example_from();
-    example_into();
+    rbe::run(SECTIONS);
 }
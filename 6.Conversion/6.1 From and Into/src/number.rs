@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt;
+
+/// 可以保存任意整数类型的数字，默认是 `i32`
+///
+/// 和整数之间的转换规则和标准库一致：
+///
+/// - 不会丢失数据的转换（例如 `u8` 到 `Number<i32>`）实现了 `From`
+/// - 可能超出范围的转换（例如 `i64` 到 `Number<u8>`）只实现了 `TryFrom`，失败的时候返回 [`OutOfRange`]
+/// - `Number<T>` 可以通过 `From` 转换回 `T`，以及所有能无损的保存 `T` 的整数类型
+///
+/// 没有实现 `From` 的转换在编译的时候就会报错，下面的测试使用 `assert_not_impl!` 检查了这些情况。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Number<T = i32> {
+    value: T,
+}
+
+impl<T> Number<T> {
+    pub fn new(value: T) -> Number<T> {
+        Number { value }
+    }
+
+    pub fn value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Number<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// 数字超出了目标类型的范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfRange {
+    /// 原来的数字
+    pub value: String,
+    /// 原来的类型，例如 `i64`
+    pub source: &'static str,
+    /// `Number` 保存的类型，例如 `u8`
+    pub target: &'static str,
+    pub min: i128,
+    pub max: u128,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{} is out of range for Number<{}>, expected {}..={}",
+            self.value, self.source, self.target, self.min, self.max
+        )
+    }
+}
+
+impl Error for OutOfRange {}
+
+// 不会丢失数据的转换，`$source` 的每一个值都能用 `$target` 表示
+macro_rules! widen {
+    ($($source:ty => $($target:ty),+;)*) => {
+        $($(
+            impl From<$source> for Number<$target> {
+                fn from(value: $source) -> Self {
+                    Number::new(<$target>::from(value))
+                }
+            }
+
+            impl From<Number<$source>> for $target {
+                fn from(number: Number<$source>) -> Self {
+                    <$target>::from(number.value)
+                }
+            }
+        )+)*
+    };
+}
+
+// 可能超出范围的转换
+macro_rules! narrow {
+    ($($source:ty => $($target:ty),+;)*) => {
+        $($(
+            impl TryFrom<$source> for Number<$target> {
+                type Error = OutOfRange;
+
+                fn try_from(value: $source) -> Result<Self, Self::Error> {
+                    <$target>::try_from(value)
+                        .map(Number::new)
+                        .map_err(|_| OutOfRange {
+                            value: value.to_string(),
+                            source: stringify!($source),
+                            target: stringify!($target),
+                            min: <$target>::MIN as i128,
+                            max: <$target>::MAX as u128,
+                        })
+                }
+            }
+        )+)*
+    };
+}
+
+// 和标准库中整数之间的 `From` 实现保持一致，包括类型转换成它自己
+widen! {
+    u8 => u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize;
+    u16 => u16, u32, u64, u128, usize, i32, i64, i128;
+    u32 => u32, u64, u128, i64, i128;
+    u64 => u64, u128, i128;
+    u128 => u128;
+    // `usize` 和 `isize` 的大小取决于平台，所以只有很少的无损转换
+    usize => usize;
+    i8 => i8, i16, i32, i64, i128, isize;
+    i16 => i16, i32, i64, i128, isize;
+    i32 => i32, i64, i128;
+    i64 => i64, i128;
+    i128 => i128;
+    isize => isize;
+}
+
+// 剩下的所有组合，`widen!` 中已经有的转换会通过标准库自动实现 `TryFrom`，不能重复实现
+narrow! {
+    u8 => i8;
+    u16 => u8, i8, i16, isize;
+    u32 => u8, u16, usize, i8, i16, i32, isize;
+    u64 => u8, u16, u32, usize, i8, i16, i32, i64, isize;
+    u128 => u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize;
+    usize => u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, isize;
+    i8 => u8, u16, u32, u64, u128, usize;
+    i16 => u8, u16, u32, u64, u128, usize, i8;
+    i32 => u8, u16, u32, u64, u128, usize, i8, i16, isize;
+    i64 => u8, u16, u32, u64, u128, usize, i8, i16, i32, isize;
+    i128 => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, isize;
+    isize => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 类型 `$ty` 没有实现特性 `$trait`，否则编译失败
+    //
+    // 如果实现了，下面两个 `AmbiguousIfImpl` 的实现都适用，`_` 无法推断出唯一的类型
+    macro_rules! assert_not_impl {
+        ($ty:ty: $trait:path) => {
+            const _: fn() = || {
+                trait AmbiguousIfImpl<A> {
+                    fn some_item() {}
+                }
+                impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+                struct Invalid;
+                impl<T: ?Sized + $trait> AmbiguousIfImpl<Invalid> for T {}
+                let _ = <$ty as AmbiguousIfImpl<_>>::some_item;
+            };
+        };
+    }
+
+    // 编译期的检查：会丢失数据的转换故意没有实现 `From`
+    assert_not_impl!(Number<u8>: From<i32>);
+    assert_not_impl!(Number<u8>: From<i8>);
+    assert_not_impl!(Number<i64>: From<u64>);
+    assert_not_impl!(Number<u64>: From<usize>);
+    assert_not_impl!(Number<usize>: From<u32>);
+    assert_not_impl!(i8: From<Number<u8>>);
+    assert_not_impl!(u32: From<Number<i32>>);
+    // 不同类型的 `Number` 之间也不能直接转换
+    assert_not_impl!(Number<i64>: From<Number<i32>>);
+
+    #[test]
+    fn widening_is_lossless() {
+        assert_eq!(Number::<i32>::from(u8::MAX).value(), 255);
+        assert_eq!(Number::<u128>::from(u64::MAX).value(), u64::MAX as u128);
+        assert_eq!(Number::<i16>::from(i8::MIN).value(), -128);
+        let number: Number = 30.into();
+        assert_eq!(number, Number::new(30));
+    }
+
+    #[test]
+    fn narrowing_reports_range() {
+        assert_eq!(Number::<u8>::try_from(200_i32), Ok(Number::new(200)));
+        assert_eq!(Number::<i8>::try_from(-128_i64), Ok(Number::new(-128)));
+
+        let error = Number::<u8>::try_from(300_i32).unwrap_err();
+        assert_eq!(error.source, "i32");
+        assert_eq!(error.target, "u8");
+        assert_eq!(
+            error.to_string(),
+            "300i32 is out of range for Number<u8>, expected 0..=255"
+        );
+
+        let error = Number::<i64>::try_from(u128::MAX).unwrap_err();
+        assert_eq!(error.value, u128::MAX.to_string());
+        assert_eq!(error.min, i64::MIN as i128);
+
+        // 标准库会为所有实现了 `From` 的转换自动实现 `TryFrom`，这里故意使用 `try_from`
+        #[allow(clippy::unnecessary_fallible_conversions)]
+        let number = Number::<i64>::try_from(5_u8);
+        assert_eq!(number, Ok(Number::new(5)));
+    }
+
+    #[test]
+    fn back_to_primitives() {
+        let number = Number::new(-7_i16);
+        assert_eq!(i16::from(number), -7);
+        assert_eq!(i64::from(number), -7);
+        let value: i128 = number.into();
+        assert_eq!(value, -7);
+        assert_eq!(u8::try_from(i32::from(Number::new(42_u8))), Ok(42));
+    }
+}
@@ -6,33 +6,50 @@
 use std::convert::TryFrom;
 use std::convert::TryInto;
 
-#[derive(Debug, PartialEq)]
-struct EvenNumber(i32);
-
-impl TryFrom<i32> for EvenNumber {
-    // 指明错误的具体类型
-    type Error = ();
-
-    // 使用 Result 来包装结果成功还是失败
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        if value % 2 == 0 {
-            Ok(EvenNumber(value))
-        } else {
-            Err(())
-        }
-    }
-}
+// 通用的 `Refined` 类型放在了单独的 `refined.rs` 文件中
+mod refined;
+
+use refined::{And, Even, InRange, Or, Positive, RefineError, Refined};
+
+// 一定是偶数的 `i32`，条件 `Even` 只存在于类型中
+type EvenNumber = Refined<i32, Even>;
 
 fn main() {
     // TryFrom
 
-    assert_eq!(EvenNumber::try_from(8), Ok(EvenNumber(8)));
-    assert_eq!(EvenNumber::try_from(5), Err(()));
+    assert_eq!(EvenNumber::try_from(8).map(Refined::into_inner), Ok(8));
+    // 错误类型 `RefineError` 会说明不满足的条件和原来的数字
+    let error = EvenNumber::try_from(5).unwrap_err();
+    println!("{}", error);
 
     // TryInto
 
-    let result: Result<EvenNumber, ()> = 8i32.try_into();
-    assert_eq!(result, Ok(EvenNumber(8)));
-    let result: Result<EvenNumber, ()> = 5i32.try_into();
-    assert_eq!(result, Err(()));
+    let result: Result<EvenNumber, RefineError> = 8i32.try_into();
+    assert_eq!(result.map(Refined::into_inner), Ok(8));
+    let result: Result<EvenNumber, RefineError> = 5i32.try_into();
+    assert!(result.is_err());
+
+    // 任意宽度的整数都可以转换，超出 `i32` 的范围也会返回错误
+    let result: Result<EvenNumber, _> = 10u8.try_into();
+    println!("{:?}", result);
+    let result: Result<EvenNumber, _> = u64::MAX.try_into();
+    println!("{:?}", result);
+
+    // 条件可以组合
+    type Score = Refined<u8, And<Positive, InRange<1, 100>>>;
+    for value in [42_i64, 0, 250, 1000] {
+        match Score::try_from(value) {
+            Ok(score) => println!("score: {}", score),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+
+    // 满足其中一个条件就可以
+    type Handicap = Refined<i32, Or<Even, InRange<-5, 5>>>;
+    for value in [-3, 8, 7] {
+        match Handicap::try_from(value) {
+            Ok(handicap) => println!("handicap: {}", handicap),
+            Err(e) => println!("error: {}", e),
+        }
+    }
 }
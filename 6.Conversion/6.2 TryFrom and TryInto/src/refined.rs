@@ -0,0 +1,303 @@
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// 数字需要满足的条件
+pub trait Predicate<T> {
+    /// 条件的描述，用于错误信息，例如 `even`
+    fn describe() -> String;
+
+    fn test(value: &T) -> bool;
+
+    /// 检查数字是否满足条件，不满足的时候返回具体是哪一个条件不满足
+    fn check(value: &T) -> Result<(), String> {
+        if Self::test(value) {
+            Ok(())
+        } else {
+            Err(Self::describe())
+        }
+    }
+}
+
+// 条件只用在类型上，和幽灵类型一样都是不能创建的空枚举
+
+/// 偶数
+pub enum Even {}
+
+/// 大于 0
+pub enum Positive {}
+
+/// 在 `MIN..=MAX` 的范围内
+pub enum InRange<const MIN: i128, const MAX: i128> {}
+
+/// 同时满足 `A` 和 `B`
+pub struct And<A, B>(PhantomData<(A, B)>);
+
+/// 满足 `A` 或者 `B` 其中一个
+pub struct Or<A, B>(PhantomData<(A, B)>);
+
+macro_rules! impl_predicates {
+    ($($t:ty),*) => {
+        $(
+            impl Predicate<$t> for Even {
+                fn describe() -> String {
+                    "even".to_string()
+                }
+
+                fn test(value: &$t) -> bool {
+                    value % 2 == 0
+                }
+            }
+
+            impl Predicate<$t> for Positive {
+                fn describe() -> String {
+                    "positive".to_string()
+                }
+
+                fn test(value: &$t) -> bool {
+                    *value > 0
+                }
+            }
+
+            impl<const MIN: i128, const MAX: i128> Predicate<$t> for InRange<MIN, MAX> {
+                fn describe() -> String {
+                    format!("in {}..={}", MIN, MAX)
+                }
+
+                // 超过 `i128::MAX` 的 `u128` 一定不在范围内
+                fn test(value: &$t) -> bool {
+                    i128::try_from(*value).is_ok_and(|value| (MIN..=MAX).contains(&value))
+                }
+            }
+        )*
+    };
+}
+
+impl_predicates!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T, A: Predicate<T>, B: Predicate<T>> Predicate<T> for And<A, B> {
+    fn describe() -> String {
+        format!("{} and {}", A::describe(), B::describe())
+    }
+
+    fn test(value: &T) -> bool {
+        A::test(value) && B::test(value)
+    }
+
+    // 只报告第一个不满足的条件
+    fn check(value: &T) -> Result<(), String> {
+        A::check(value)?;
+        B::check(value)
+    }
+}
+
+impl<T, A: Predicate<T>, B: Predicate<T>> Predicate<T> for Or<A, B> {
+    fn describe() -> String {
+        format!("{} or {}", A::describe(), B::describe())
+    }
+
+    fn test(value: &T) -> bool {
+        A::test(value) || B::test(value)
+    }
+}
+
+/// 转换成 `Refined` 失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefineError {
+    /// 数字不能用 `target` 类型表示
+    OutOfRange { value: String, target: &'static str },
+    /// 数字不满足条件 `predicate`
+    Violated { value: String, predicate: String },
+}
+
+impl fmt::Display for RefineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefineError::OutOfRange { value, target } => {
+                write!(f, "{} does not fit in {}", value, target)
+            }
+            RefineError::Violated { value, predicate } => {
+                write!(f, "{} is not {}", value, predicate)
+            }
+        }
+    }
+}
+
+impl Error for RefineError {}
+
+/// 一定满足条件 `P` 的 `T` 类型的数字
+///
+/// 只能通过 [`Refined::new`] 或者 `TryFrom` 创建，所以拿到 `Refined` 的代码不需要再次检查。
+pub struct Refined<T, P> {
+    value: T,
+    predicate: PhantomData<P>,
+}
+
+impl<T: fmt::Display, P: Predicate<T>> Refined<T, P> {
+    pub fn new(value: T) -> Result<Refined<T, P>, RefineError> {
+        match P::check(&value) {
+            Ok(()) => Ok(Refined {
+                value,
+                predicate: PhantomData,
+            }),
+            Err(predicate) => Err(RefineError::Violated {
+                value: value.to_string(),
+                predicate,
+            }),
+        }
+    }
+}
+
+impl<T, P> Refined<T, P> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, P> Deref for Refined<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// 条件 `P` 只是一个标记，和第 14.9 章的 `Length` 一样手动实现这些特性，原因见 `length.rs`
+impl<T: Clone, P> Clone for Refined<T, P> {
+    fn clone(&self) -> Self {
+        Refined {
+            value: self.value.clone(),
+            predicate: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, P> Copy for Refined<T, P> {}
+
+impl<T: PartialEq, P> PartialEq for Refined<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: fmt::Debug, P> fmt::Debug for Refined<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Refined").field(&self.value).finish()
+    }
+}
+
+impl<T: fmt::Display, P> fmt::Display for Refined<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+// 任意宽度的整数都可以转换，先转换成 `T`，再检查条件
+macro_rules! impl_try_from {
+    ($($source:ty),*) => {
+        $(
+            impl<T, P> TryFrom<$source> for Refined<T, P>
+            where
+                T: TryFrom<$source> + fmt::Display,
+                P: Predicate<T>,
+            {
+                type Error = RefineError;
+
+                fn try_from(value: $source) -> Result<Self, Self::Error> {
+                    let value = T::try_from(value).map_err(|_| RefineError::OutOfRange {
+                        value: value.to_string(),
+                        target: type_name::<T>(),
+                    })?;
+                    Refined::new(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type EvenNumber = Refined<i32, Even>;
+    type Percent = Refined<u8, InRange<0, 100>>;
+    type PositiveEven = Refined<i64, And<Positive, Even>>;
+
+    #[test]
+    fn even_numbers() {
+        assert_eq!(EvenNumber::try_from(8).map(Refined::into_inner), Ok(8));
+        assert_eq!(*EvenNumber::try_from(-4).unwrap(), -4);
+
+        let error = EvenNumber::try_from(5_i32).unwrap_err();
+        assert_eq!(
+            error,
+            RefineError::Violated {
+                value: "5".to_string(),
+                predicate: "even".to_string()
+            }
+        );
+        assert_eq!(error.to_string(), "5 is not even");
+    }
+
+    #[test]
+    fn every_integer_width() {
+        assert!(EvenNumber::try_from(8_u8).is_ok());
+        assert!(EvenNumber::try_from(8_i128).is_ok());
+        assert!(EvenNumber::try_from(8_usize).is_ok());
+        assert_eq!(
+            EvenNumber::try_from(u64::MAX).unwrap_err().to_string(),
+            "18446744073709551615 does not fit in i32"
+        );
+        assert_eq!(
+            Refined::<u128, Even>::try_from(-2_i8)
+                .unwrap_err()
+                .to_string(),
+            "-2 does not fit in u128"
+        );
+        assert!(Refined::<u128, InRange<0, 10>>::new(u128::MAX).is_err());
+    }
+
+    #[test]
+    fn ranges() {
+        assert!(Percent::try_from(0_i32).is_ok());
+        assert!(Percent::try_from(100_i32).is_ok());
+        assert_eq!(
+            Percent::try_from(101_i32).unwrap_err().to_string(),
+            "101 is not in 0..=100"
+        );
+        assert!(Refined::<i8, InRange<-10, -1>>::new(-10).is_ok());
+        assert!(Refined::<i8, InRange<-10, -1>>::new(0).is_err());
+    }
+
+    #[test]
+    fn combinators() {
+        assert!(PositiveEven::try_from(4_i64).is_ok());
+        // `And` 报告第一个不满足的条件
+        assert_eq!(
+            PositiveEven::try_from(-4_i64).unwrap_err().to_string(),
+            "-4 is not positive"
+        );
+        assert_eq!(
+            PositiveEven::try_from(3_i64).unwrap_err().to_string(),
+            "3 is not even"
+        );
+
+        type EvenOrSmall = Refined<i32, Or<Even, InRange<1, 3>>>;
+        assert!(EvenOrSmall::new(100).is_ok());
+        assert!(EvenOrSmall::new(3).is_ok());
+        assert_eq!(
+            EvenOrSmall::new(5).unwrap_err().to_string(),
+            "5 is not even or in 1..=3"
+        );
+    }
+
+    #[test]
+    fn error_trait() {
+        let error: Box<dyn Error> = Box::new(EvenNumber::try_from(1_i32).unwrap_err());
+        assert_eq!(error.to_string(), "1 is not even");
+    }
+}
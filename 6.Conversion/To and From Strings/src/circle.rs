@@ -0,0 +1,230 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Circle {
+    pub radius: i32,
+}
+
+/// 很多时候需要把数据结构转化成字符串，Rust提供了 ToString 特性，
+/// 只需要实现 ToString 特性就可以把结构转换成 String类型，
+/// 但是这个方法只能给结构体提供一个 to_string() 方法，
+/// 而且不能自动支持类似 println!() 的使用，
+/// 通常我们实现的是 fmt::Display 特性，实现了该特性，会自动实现 ToString 特性
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Circle {{ radius: {} }}", self.radius)
+    }
+}
+
+/// 解析 `Circle` 失败的原因，`position` 是出错的位置在原来的字符串中的字节偏移
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCircleError {
+    /// 在 `position` 处应该是 `expected`
+    Expected {
+        expected: &'static str,
+        position: usize,
+    },
+    /// 半径不是合法的 `i32`
+    InvalidRadius {
+        error: ParseIntError,
+        position: usize,
+    },
+}
+
+impl ParseCircleError {
+    pub fn position(&self) -> usize {
+        match self {
+            ParseCircleError::Expected { position, .. }
+            | ParseCircleError::InvalidRadius { position, .. } => *position,
+        }
+    }
+}
+
+impl fmt::Display for ParseCircleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCircleError::Expected { expected, position } => {
+                write!(f, "expected {} at position {}", expected, position)
+            }
+            ParseCircleError::InvalidRadius { error, position } => {
+                write!(f, "invalid radius at position {}: {}", position, error)
+            }
+        }
+    }
+}
+
+impl Error for ParseCircleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseCircleError::InvalidRadius { error, .. } => Some(error),
+            ParseCircleError::Expected { .. } => None,
+        }
+    }
+}
+
+// 记录当前解析到的位置，出错的时候可以报告位置
+struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    // 跳过空白以后，接下来的内容必须是 `token`
+    fn expect(
+        &mut self,
+        token: &'static str,
+        expected: &'static str,
+    ) -> Result<(), ParseCircleError> {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            Ok(())
+        } else {
+            Err(ParseCircleError::Expected {
+                expected,
+                position: self.position,
+            })
+        }
+    }
+
+    // 半径一直到空白或者 `}` 为止
+    fn radius(&mut self) -> Result<i32, ParseCircleError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '}')
+            .unwrap_or(rest.len());
+        let position = self.position;
+        self.position += len;
+        rest[..len]
+            .parse()
+            .map_err(|error| ParseCircleError::InvalidRadius { error, position })
+    }
+
+    fn end(&mut self) -> Result<(), ParseCircleError> {
+        self.skip_whitespace();
+        if self.rest().is_empty() {
+            Ok(())
+        } else {
+            Err(ParseCircleError::Expected {
+                expected: "end of input",
+                position: self.position,
+            })
+        }
+    }
+}
+
+/// 实现 FromStr 的话，就可以通过 str 来构造对应的类型了
+///
+/// 可以只有半径，例如 `6`，也可以是 `Display` 输出的格式，例如 `Circle { radius: 6 }`，
+/// 前后和各个部分之间可以有任意的空白。
+impl FromStr for Circle {
+    type Err = ParseCircleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor {
+            input: s,
+            position: 0,
+        };
+        cursor.skip_whitespace();
+
+        let radius = if cursor.rest().starts_with("Circle") {
+            cursor.expect("Circle", "`Circle`")?;
+            cursor.expect("{", "`{`")?;
+            cursor.expect("radius", "`radius`")?;
+            cursor.expect(":", "`:`")?;
+            let radius = cursor.radius()?;
+            cursor.expect("}", "`}`")?;
+            radius
+        } else {
+            cursor.radius()?
+        };
+
+        cursor.end()?;
+        Ok(Circle { radius })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::IntErrorKind;
+
+    #[test]
+    fn both_forms() {
+        assert_eq!("6".parse(), Ok(Circle { radius: 6 }));
+        assert_eq!("  -6 \n".parse(), Ok(Circle { radius: -6 }));
+        assert_eq!("Circle { radius: 6 }".parse(), Ok(Circle { radius: 6 }));
+        assert_eq!("Circle{radius:6}".parse(), Ok(Circle { radius: 6 }));
+        assert_eq!(
+            "\tCircle  {\n  radius :  +6\n}  ".parse(),
+            Ok(Circle { radius: 6 })
+        );
+    }
+
+    #[test]
+    fn error_positions() {
+        let error = "Circle ( radius: 6 }".parse::<Circle>().unwrap_err();
+        assert_eq!(error.position(), 7);
+        assert_eq!(error.to_string(), "expected `{` at position 7");
+
+        let error = "Circle { radius: six }".parse::<Circle>().unwrap_err();
+        assert_eq!(error.position(), 17);
+        assert_eq!(
+            error.to_string(),
+            "invalid radius at position 17: invalid digit found in string"
+        );
+        assert!(error.source().is_some());
+
+        let error = "Circle { radius: 6 ".parse::<Circle>().unwrap_err();
+        assert_eq!(error.to_string(), "expected `}` at position 19");
+
+        let error = "6 7".parse::<Circle>().unwrap_err();
+        assert_eq!(error.to_string(), "expected end of input at position 2");
+    }
+
+    #[test]
+    fn wraps_parse_int_error() {
+        match "99999999999".parse::<Circle>() {
+            Err(ParseCircleError::InvalidRadius { error, position: 0 }) => {
+                assert_eq!(error.kind(), &IntErrorKind::PosOverflow)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match "Circle { radius: }".parse::<Circle>() {
+            Err(ParseCircleError::InvalidRadius {
+                error,
+                position: 17,
+            }) => {
+                assert_eq!(error.kind(), &IntErrorKind::Empty)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!("".parse::<Circle>().unwrap_err().position(), 0);
+    }
+
+    #[test]
+    fn round_trip_all_radii() {
+        // 所有的 `i32` 太多了，所以使用边界值和均匀分布在整个范围内的值
+        let edges = [i32::MIN, i32::MIN + 1, -1, 0, 1, i32::MAX - 1, i32::MAX];
+        let spread = (i32::MIN..=i32::MAX).step_by(65_537);
+        for radius in edges.into_iter().chain(spread) {
+            let circle = Circle { radius };
+            assert_eq!(circle.to_string().parse(), Ok(circle));
+            assert_eq!(radius.to_string().parse(), Ok(circle));
+            assert_eq!(format!("  {}\n", circle).parse(), Ok(circle));
+        }
+    }
+}
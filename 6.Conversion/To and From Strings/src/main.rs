@@ -1,30 +1,10 @@
-use std::{fmt, str::FromStr};
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(clippy::to_string_in_format_args)]
 
-struct Circle {
-    radius: i32,
-}
+// `Circle` 以及 `Display` 和 `FromStr` 的实现放在了单独的 `circle.rs` 文件中
+mod circle;
 
-/// 很多时候需要把数据结构转化成字符串，Rust提供了 ToString 特性，
-/// 只需要实现 ToString 特性就可以把结构转换成 String类型，
-/// 但是这个方法只能给结构体提供一个 to_string() 方法，
-/// 而且不能自动支持类似 println!() 的使用，
-/// 通常我们实现的是 fmt::Display 特性，实现了该特性，会自动实现 ToString 特性
-impl fmt::Display for Circle {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Circle {{ radius: {} }}", self.radius)
-    }
-}
-
-/// 实现 FromStr 的话，就可以通过 str 来构造对应的类型了
-impl FromStr for Circle {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.parse() {
-            Ok(v) => Ok(Circle { radius: v }),
-            Err(_) => Err(()),
-        }
-    }
-}
+use circle::Circle;
 
 fn main() {
     let circle = Circle { radius: 6 };
@@ -35,5 +15,25 @@ fn main() {
     // 使用 通过实现 fmt::Dispaly 特性提供的 to_string() 方法来获得字符串
     println!("{}", circle.to_string());
 
-    assert_eq!(circle.radius, circle2.radius)
+    assert_eq!(circle.radius, circle2.radius);
+
+    // `Display` 输出的字符串也可以解析回来
+    let circle3: Circle = circle.to_string().parse().unwrap();
+    assert_eq!(circle, circle3);
+
+    // 解析失败的时候，错误会说明出错的位置
+    for s in [
+        " Circle {  radius : 8 } ",
+        "Circle { radius: 6.5 }",
+        "Circle [ radius: 6 ]",
+    ] {
+        match s.parse::<Circle>() {
+            Ok(circle) => println!("{:?} => {}", s, circle),
+            Err(e) => {
+                println!("{:?} => error: {}", s, e);
+                // 使用 `^` 标出出错的位置，`{:?}` 输出的字符串前面多了一个引号
+                println!("{:>width$}", "^", width = e.position() + 2);
+            }
+        }
+    }
 }
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// 数量的种类，重量和体积统一使用克和毫升保存
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Measure {
+    /// 个数，没有单位
+    Count,
+    Mass,
+    Volume,
+}
+
+impl Measure {
+    fn symbol(self) -> &'static str {
+        match self {
+            Measure::Count => "",
+            Measure::Mass => " g",
+            Measure::Volume => " ml",
+        }
+    }
+}
+
+// 支持的单位，以及换算成克或者毫升的倍数
+const UNITS: [(&str, Measure, u64); 4] = [
+    ("g", Measure::Mass, 1),
+    ("kg", Measure::Mass, 1000),
+    ("ml", Measure::Volume, 1),
+    ("l", Measure::Volume, 1000),
+];
+
+/// 数量，使用千分之一保存，所以最多支持三位小数，相加的时候不会有浮点数的误差
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Amount {
+    pub thousandths: u64,
+    pub measure: Measure,
+}
+
+// 去掉末尾的 0，例如 `1.500` 输出 `1.5`
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (whole, fraction) = (self.thousandths / 1000, self.thousandths % 1000);
+        if fraction == 0 {
+            write!(f, "{}", whole)?;
+        } else {
+            let fraction = format!("{:03}", fraction);
+            write!(f, "{}.{}", whole, fraction.trim_end_matches('0'))?;
+        }
+        f.write_str(self.measure.symbol())
+    }
+}
+
+/// 清单中的一行，例如 `1.5 kg apples`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineItem {
+    pub amount: Amount,
+    pub item: String,
+}
+
+/// 出错的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 数量不合法，例如 `three`、`1.2345` 或者超出了范围
+    InvalidQuantity(String),
+    /// 不认识的单位，例如 `3lb`
+    UnknownUnit(String),
+    /// 只有数量没有物品
+    MissingItem,
+    /// 同一个物品使用了不同种类的单位，例如先是 `1 kg` 然后是 `2 l`
+    MeasureMismatch { item: String, expected: Measure },
+}
+
+/// 解析失败的位置和原因，行号和列号都从 1 开始，列号按照字符计算
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::InvalidQuantity(s) => write!(f, "invalid quantity `{}`", s),
+            ErrorKind::UnknownUnit(s) => write!(f, "unknown unit `{}`", s),
+            ErrorKind::MissingItem => f.write_str("missing item name"),
+            ErrorKind::MeasureMismatch { item, expected } => {
+                write!(f, "`{}` was measured as {:?} before", item, expected)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// 字节偏移转换成从 1 开始的列号
+fn column(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+// 解析最多三位小数的数量，结果是千分之一
+fn parse_thousandths(s: &str) -> Option<u64> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !all_digits(whole) || !all_digits(fraction) || fraction.len() > 3 {
+        return None;
+    }
+    let whole: u64 = whole.parse().ok()?;
+    let fraction: u64 = format!("{:0<3}", fraction).parse().ok()?;
+    whole.checked_mul(1000)?.checked_add(fraction)
+}
+
+/// 解析一行，空行和 `#` 开头的注释返回 `Ok(None)`
+///
+/// 格式是 `数量 [单位] 物品`，单位可以和数量连在一起，例如 `3 chairs`、`1.5kg apples`、`2 l milk`。
+/// `line_number` 只用于错误信息。
+pub fn parse_line(line: &str, line_number: usize) -> Result<Option<LineItem>, ParseError> {
+    let error = |offset: usize, kind: ErrorKind| ParseError {
+        line: line_number,
+        column: column(line, offset),
+        kind,
+    };
+
+    let content = line.trim_start();
+    let start = line.len() - content.len();
+    // 空行和注释直接跳过
+    let Some(first) = content.chars().next() else {
+        return Ok(None);
+    };
+    if first == '#' {
+        return Ok(None);
+    }
+
+    // 第一个单词是数量，后面可以直接跟着单位
+    let token_len = content.find(char::is_whitespace).unwrap_or(content.len());
+    let token = &content[..token_len];
+    let number_len = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(token.len());
+    let (number, mut unit) = token.split_at(number_len);
+    let Some(mut thousandths) = parse_thousandths(number) else {
+        return Err(error(start, ErrorKind::InvalidQuantity(token.to_string())));
+    };
+
+    let mut rest = &content[token_len..];
+    let mut unit_offset = start + number_len;
+    // 数量和单位分开的时候，只有认识的单位才当做单位，否则就是物品名称的一部分
+    if unit.is_empty() {
+        let after = rest.trim_start();
+        let word_len = after.find(char::is_whitespace).unwrap_or(after.len());
+        if UNITS.iter().any(|&(name, _, _)| name == &after[..word_len]) {
+            unit_offset = line.len() - after.len();
+            unit = &after[..word_len];
+            rest = &after[word_len..];
+        }
+    }
+
+    let measure = if unit.is_empty() {
+        Measure::Count
+    } else {
+        let Some(&(_, measure, scale)) = UNITS.iter().find(|&&(name, _, _)| name == unit) else {
+            return Err(error(unit_offset, ErrorKind::UnknownUnit(unit.to_string())));
+        };
+        let Some(scaled) = thousandths.checked_mul(scale) else {
+            return Err(error(start, ErrorKind::InvalidQuantity(token.to_string())));
+        };
+        thousandths = scaled;
+        measure
+    };
+
+    let item = rest.trim();
+    if item.is_empty() {
+        return Err(error(line.trim_end().len(), ErrorKind::MissingItem));
+    }
+
+    Ok(Some(LineItem {
+        amount: Amount {
+            thousandths,
+            measure,
+        },
+        item: item.to_string(),
+    }))
+}
+
+/// 解析多行的清单，遇到第一个错误的时候停止
+pub fn parse_list(text: &str) -> Result<Vec<LineItem>, ParseError> {
+    let mut items = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let Some(item) = parse_line(line, index + 1)? else {
+            continue;
+        };
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// 解析清单并把相同的物品加在一起
+///
+/// 同一个物品的单位可以不同，例如 `500 g flour` 和 `1 kg flour`，但是种类必须相同。
+pub fn aggregate(text: &str) -> Result<HashMap<String, Amount>, ParseError> {
+    let mut totals: HashMap<String, Amount> = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let Some(LineItem { amount, item }) = parse_line(line, index + 1)? else {
+            continue;
+        };
+        let Some(total) = totals.get_mut(&item) else {
+            totals.insert(item, amount);
+            continue;
+        };
+
+        let error = |kind| ParseError {
+            line: index + 1,
+            column: column(line, line.len() - line.trim_start().len()),
+            kind,
+        };
+        if total.measure != amount.measure {
+            return Err(error(ErrorKind::MeasureMismatch {
+                item,
+                expected: total.measure,
+            }));
+        }
+        let Some(sum) = total.thousandths.checked_add(amount.thousandths) else {
+            return Err(error(ErrorKind::InvalidQuantity(amount.to_string())));
+        };
+        total.thousandths = sum;
+    }
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(thousandths: u64, measure: Measure) -> Amount {
+        Amount {
+            thousandths,
+            measure,
+        }
+    }
+
+    #[test]
+    fn single_lines() {
+        let item = parse_line("3 chairs", 1).unwrap().unwrap();
+        assert_eq!(item.amount, amount(3000, Measure::Count));
+        assert_eq!(item.item, "chairs");
+
+        let item = parse_line("  1.5kg  red apples ", 1).unwrap().unwrap();
+        assert_eq!(item.amount.to_string(), "1500 g");
+        assert_eq!(item.item, "red apples");
+
+        let item = parse_line("0.25 l milk", 1).unwrap().unwrap();
+        assert_eq!(item.amount.to_string(), "250 ml");
+        // 不认识的单词不是单位，是物品名称的一部分
+        let item = parse_line("2 large boxes", 1).unwrap().unwrap();
+        assert_eq!(item.item, "large boxes");
+
+        assert_eq!(parse_line("   ", 1), Ok(None));
+        assert_eq!(parse_line("# comment", 1), Ok(None));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let error = |line: &str| parse_line(line, 7).unwrap_err();
+
+        let e = error("  three chairs");
+        assert_eq!((e.line, e.column), (7, 3));
+        assert_eq!(e.to_string(), "line 7, column 3: invalid quantity `three`");
+
+        let e = error("1.2345 kg flour");
+        assert_eq!(e.kind, ErrorKind::InvalidQuantity("1.2345".to_string()));
+
+        let e = error("3lb sugar");
+        assert_eq!(
+            (e.column, e.kind),
+            (2, ErrorKind::UnknownUnit("lb".to_string()))
+        );
+
+        let e = error("2 kg  ");
+        assert_eq!((e.column, e.kind), (5, ErrorKind::MissingItem));
+
+        // 列号按照字符计算
+        let e = error("１ chairs");
+        assert_eq!(e.column, 1);
+        let e = error("苹果 3");
+        assert_eq!(e.kind, ErrorKind::InvalidQuantity("苹果".to_string()));
+
+        let e = error("99999999999999999999 chairs");
+        assert_eq!(
+            e.kind,
+            ErrorKind::InvalidQuantity("99999999999999999999".to_string())
+        );
+    }
+
+    #[test]
+    fn multi_line_list() {
+        let text = "# weekly shopping\n3 chairs\n\n1.5 kg apples\n";
+        let items = parse_list(text).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].item, "apples");
+
+        let e = parse_list("3 chairs\n\n  x apples").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+    }
+
+    #[test]
+    fn aggregate_duplicates() {
+        let text = "\
+            500 g flour\n\
+            2 eggs\n\
+            1.25kg flour\n\
+            1 eggs\n\
+            0.5 l milk\n\
+            250 ml milk\n";
+        let totals = aggregate(text).unwrap();
+        assert_eq!(totals.len(), 3);
+        assert_eq!(totals["flour"], amount(1_750_000, Measure::Mass));
+        assert_eq!(totals["eggs"].to_string(), "3");
+        assert_eq!(totals["milk"].to_string(), "750 ml");
+
+        let e = aggregate("1 kg rice\n  2 l rice").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 3: `rice` was measured as Mass before"
+        );
+    }
+}
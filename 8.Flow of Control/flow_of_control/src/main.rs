@@ -1,5 +1,24 @@
 //! 控制流
 
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(
+    unused_variables,
+    clippy::disallowed_names,
+    clippy::match_single_binding,
+    clippy::never_loop,
+    clippy::redundant_guards,
+    clippy::single_match,
+    clippy::toplevel_ref_arg,
+    clippy::upper_case_acronyms,
+    clippy::while_let_loop
+)]
+
+// 完整的温度类型放在了单独的 `temperature.rs` 文件中
+mod temperature;
+
+// 不会 `panic!` 的清单解析放在了单独的 `inventory.rs` 文件中
+mod inventory;
+
 /// 分支控制 if-else 和大多数语言一样，
 /// 但是 不需要使用 () 来包围逻辑表达式
 /// 每一个分支都使用一个 {} 来包围语句块，
@@ -434,6 +453,28 @@ fn match_guards() {
         }
     }
 
+    {
+        use temperature::{Scale, Temperature};
+
+        // 守卫中可以使用任意的表达式，例如比较不同温标的温度
+        let fever: Temperature = "38C".parse().unwrap();
+        for reading in ["36.6C", "101.3F", "310.15K", "-300C"] {
+            match reading.parse::<Temperature>() {
+                Ok(t) if t >= fever => println!("{} ({}) is a fever", t, t.to(Scale::Celsius)),
+                Ok(t) if t.scale() == Scale::Celsius => println!("{} is normal", t),
+                // 上面的守卫不满足的时候会继续匹配下面的分支
+                Ok(t) => println!("{} ({}) is normal", t, t.to(Scale::Celsius)),
+                Err(e) => println!("can't read {:?}: {}", reading, e),
+            }
+        }
+
+        // 同一个温度在不同温标下的读数
+        let normal = Temperature::new(37.0, Scale::Celsius).unwrap();
+        for scale in Scale::ALL {
+            println!("normal: {}", normal.to(scale));
+        }
+    }
+
     {
         let number: u8 = 4;
 
@@ -637,6 +678,31 @@ fn let_else() {
 
         assert_eq!(get_count_item("3 chairs"), (3, "chairs"));
     }
+
+    // `let else` 也可以用来提前返回错误，而不是 `panic!`，`inventory.rs` 中的解析就是这样写的
+    {
+        let list = "\
+# 购物清单
+3 chairs
+1.5 kg apples
+2 chairs
+500g apples
+";
+        // `parse_list` 返回每一行的内容，`aggregate` 把相同的物品加在一起
+        let lines = inventory::parse_list(list).unwrap();
+        println!("{} lines", lines.len());
+        let totals = inventory::aggregate(list).unwrap();
+        let mut items: Vec<_> = totals.iter().collect();
+        items.sort_by_key(|&(item, _)| item);
+        for (item, amount) in items {
+            println!("{} {}", amount, item);
+        }
+
+        let Err(e) = inventory::aggregate("3 chairs\nthree tables") else {
+            unreachable!("`three` is not a number");
+        };
+        println!("error: {}", e);
+    }
 }
 
 fn while_let() {
@@ -688,30 +754,34 @@ fn while_let() {
     }
 }
 
-fn main() {
+rbe::sections! {
     // 分支语句
-    if_else();
+    if_else: "`if` `else` 分支",
 
     // 循环语句
-    loop_();
-    nesting_and_labels();
-    returning_from_loops();
-    while_();
-    for_and_range();
-    for_and_iterators();
+    loop_: "`loop` 无限循环",
+    nesting_and_labels: "嵌套循环和标签",
+    returning_from_loops: "从 `loop` 中返回值",
+    while_: "`while` 循环",
+    for_and_range: "`for` 和区间",
+    for_and_iterators: "`for` 和迭代器",
 
     // 匹配语句
-    match_();
-    match_tuples();
-    match_array_slice();
-    match_enum();
-    match_points_or_ref();
-    match_struct();
-    match_guards();
-    match_binding();
+    match_: "`match` 匹配",
+    match_tuples: "解构元组",
+    match_array_slice: "解构数组和切片",
+    match_enum: "解构枚举",
+    match_points_or_ref: "解构指针和引用",
+    match_struct: "解构结构体",
+    match_guards: "匹配守卫",
+    match_binding: "匹配绑定",
 
     // 判断解构
-    if_let();
-    let_else();
-    while_let();
+    if_let: "`if let`",
+    let_else: "`let else`",
+    while_let: "`while let`",
+}
+
+fn main() {
+    rbe::run(SECTIONS);
 }
@@ -0,0 +1,352 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// 温标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+// 内部使用整数保存温度，单位是 1/900 开尔文，从绝对零度开始计算，所以不会是负数。
+// 这样三种温标精确到 0.01 度的读数都是整数：0.01°C 和 0.01K 是 9，0.01°F 是 5，
+// 不同温标之间的转换只是换一种方式显示同一个整数，所以转换是精确的，不会累积误差。
+const TICKS_PER_KELVIN: i64 = 900;
+
+impl Scale {
+    pub const ALL: [Scale; 3] = [Scale::Celsius, Scale::Fahrenheit, Scale::Kelvin];
+
+    pub fn symbol(self) -> char {
+        match self {
+            Scale::Celsius => 'C',
+            Scale::Fahrenheit => 'F',
+            Scale::Kelvin => 'K',
+        }
+    }
+
+    // 这个温标的 0 度对应的内部整数，以及 1 度对应多少个内部单位
+    //
+    // 0°C = 273.15K，0°F = 459.67 * 5 / 9 K，1°F = 5 / 9 K
+    fn zero_and_degree(self) -> (i64, i64) {
+        match self {
+            Scale::Celsius => (27315 * 9, TICKS_PER_KELVIN),
+            Scale::Fahrenheit => (45967 * 5, 500),
+            Scale::Kelvin => (0, TICKS_PER_KELVIN),
+        }
+    }
+}
+
+/// 温度读数，不会低于绝对零度
+///
+/// 读数会记住自己使用的温标，`Display` 使用这个温标输出，
+/// 比较和相等的判断与温标无关，例如 `0C == 32F`。
+#[derive(Debug, Clone, Copy)]
+pub struct Temperature {
+    ticks: i64,
+    scale: Scale,
+}
+
+/// 创建或者解析温度失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemperatureError {
+    /// 没有温标，例如 `36.6`
+    MissingScale,
+    /// 不认识的温标，例如 `36.6X`
+    UnknownScale(char),
+    /// 数字不合法，或者超过了两位小数
+    InvalidNumber(String),
+    /// 低于绝对零度
+    BelowAbsoluteZero(String),
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemperatureError::MissingScale => f.write_str("missing scale, expected C, F or K"),
+            TemperatureError::UnknownScale(c) => {
+                write!(f, "unknown scale `{}`, expected C, F or K", c)
+            }
+            TemperatureError::InvalidNumber(s) => write!(f, "invalid number `{}`", s),
+            TemperatureError::BelowAbsoluteZero(s) => write!(f, "{} is below absolute zero", s),
+        }
+    }
+}
+
+impl Error for TemperatureError {}
+
+impl Temperature {
+    /// 使用百分之一度创建温度，例如 `from_hundredths(3660, Scale::Celsius)` 是 36.6°C
+    pub fn from_hundredths(hundredths: i64, scale: Scale) -> Result<Temperature, TemperatureError> {
+        let (zero, degree) = scale.zero_and_degree();
+        let below_zero = || {
+            TemperatureError::BelowAbsoluteZero(format!(
+                "{}{}",
+                Hundredths(hundredths),
+                scale.symbol()
+            ))
+        };
+        // 1 度是 `degree` 个内部单位，所以 0.01 度是 `degree / 100` 个
+        let ticks = hundredths
+            .checked_mul(degree / 100)
+            .and_then(|ticks| ticks.checked_add(zero))
+            .ok_or_else(|| TemperatureError::InvalidNumber(Hundredths(hundredths).to_string()))?;
+        if ticks < 0 {
+            return Err(below_zero());
+        }
+        Ok(Temperature { ticks, scale })
+    }
+
+    /// 使用浮点数创建温度，会四舍五入到 0.01 度
+    pub fn new(degrees: f64, scale: Scale) -> Result<Temperature, TemperatureError> {
+        let hundredths = (degrees * 100.0).round();
+        if !hundredths.is_finite() || hundredths.abs() >= i64::MAX as f64 {
+            return Err(TemperatureError::InvalidNumber(degrees.to_string()));
+        }
+        Temperature::from_hundredths(hundredths as i64, scale)
+    }
+
+    pub fn scale(self) -> Scale {
+        self.scale
+    }
+
+    /// 转换成另一个温标，转换是精确的，转换回来的时候和原来完全相同
+    pub fn to(self, scale: Scale) -> Temperature {
+        Temperature {
+            ticks: self.ticks,
+            scale,
+        }
+    }
+
+    /// 在指定的温标下的度数
+    pub fn degrees(self, scale: Scale) -> f64 {
+        let (zero, degree) = scale.zero_and_degree();
+        // 分子和分母都是精确的整数，只有最后的除法有一次舍入
+        (self.ticks - zero) as f64 / degree as f64
+    }
+}
+
+impl PartialEq for Temperature {
+    fn eq(&self, other: &Self) -> bool {
+        self.ticks == other.ticks
+    }
+}
+
+impl Eq for Temperature {}
+
+impl Hash for Temperature {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ticks.hash(state);
+    }
+}
+
+// 比较的时候不关心温标，例如 `100C > 200F`
+impl PartialOrd for Temperature {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Temperature {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ticks.cmp(&other.ticks)
+    }
+}
+
+// 百分之一度，输出的时候去掉末尾的 0，例如 `3660` 输出 `36.6`
+struct Hundredths(i64);
+
+impl fmt::Display for Hundredths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let (whole, fraction) = (self.0.unsigned_abs() / 100, self.0.unsigned_abs() % 100);
+        match fraction {
+            0 => write!(f, "{}{}", sign, whole),
+            _ if fraction % 10 == 0 => write!(f, "{}{}.{}", sign, whole, fraction / 10),
+            _ => write!(f, "{}{}.{:02}", sign, whole, fraction),
+        }
+    }
+}
+
+/// 使用自己的温标输出，例如 `36.6C`
+///
+/// 默认保留最多两位小数并去掉末尾的 0，也可以指定精度，例如 `{:.3}`。
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (zero, degree) = self.scale.zero_and_degree();
+        let offset = (self.ticks - zero) as i128;
+        let degree = degree as i128;
+        match f.precision() {
+            None => {
+                // 四舍五入到百分之一度，远离 0 的方向舍入
+                let hundredths = (offset * 200 + offset.signum() * degree) / (degree * 2);
+                write!(
+                    f,
+                    "{}{}",
+                    Hundredths(hundredths as i64),
+                    self.scale.symbol()
+                )
+            }
+            Some(precision) => {
+                write!(
+                    f,
+                    "{:.*}{}",
+                    precision,
+                    self.degrees(self.scale),
+                    self.scale.symbol()
+                )
+            }
+        }
+    }
+}
+
+/// 解析 `36.6C`、`98F`、`-40 °C`、`300k` 这样的字符串，最多两位小数
+impl FromStr for Temperature {
+    type Err = TemperatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some(symbol) = s.chars().last() else {
+            return Err(TemperatureError::MissingScale);
+        };
+        let scale = match symbol.to_ascii_uppercase() {
+            'C' => Scale::Celsius,
+            'F' => Scale::Fahrenheit,
+            'K' => Scale::Kelvin,
+            c if c.is_ascii_digit() || c == '.' => return Err(TemperatureError::MissingScale),
+            c => return Err(TemperatureError::UnknownScale(c)),
+        };
+        let number = s[..s.len() - symbol.len_utf8()].trim_end();
+        let number = number.strip_suffix('°').unwrap_or(number).trim_end();
+        Temperature::from_hundredths(parse_hundredths(number)?, scale)
+    }
+}
+
+// 解析最多两位小数的数字，结果是百分之一
+fn parse_hundredths(s: &str) -> Result<i64, TemperatureError> {
+    let invalid = || TemperatureError::InvalidNumber(s.to_string());
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() && fraction.is_empty() || !all_digits(whole) || !all_digits(fraction) {
+        return Err(invalid());
+    }
+    if fraction.len() > 2 {
+        return Err(invalid());
+    }
+
+    let whole: i64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| invalid())?
+    };
+    let fraction: i64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+    let hundredths = whole
+        .checked_mul(100)
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(invalid)?;
+    Ok(if negative { -hundredths } else { hundredths })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(s: &str) -> Temperature {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(t("36.6C").to_string(), "36.6C");
+        assert_eq!(t("98F").to_string(), "98F");
+        assert_eq!(t(" -40 °c ").to_string(), "-40C");
+        assert_eq!(t("0.05K").to_string(), "0.05K");
+        assert_eq!(t("+.5C").to_string(), "0.5C");
+        assert_eq!(format!("{:.3}", t("98F").to(Scale::Celsius)), "36.667C");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |s: &str| s.parse::<Temperature>().unwrap_err();
+        assert_eq!(error(""), TemperatureError::MissingScale);
+        assert_eq!(error("36.6"), TemperatureError::MissingScale);
+        assert_eq!(error("36.6X"), TemperatureError::UnknownScale('X'));
+        assert_eq!(
+            error("36.666C"),
+            TemperatureError::InvalidNumber("36.666".to_string())
+        );
+        assert_eq!(
+            error("1e3C"),
+            TemperatureError::InvalidNumber("1e3".to_string())
+        );
+        assert_eq!(
+            error(".C"),
+            TemperatureError::InvalidNumber(".".to_string())
+        );
+        assert_eq!(
+            error("--1C"),
+            TemperatureError::InvalidNumber("--1".to_string())
+        );
+        assert_eq!(
+            error("99999999999999999999C"),
+            TemperatureError::InvalidNumber("99999999999999999999".to_string())
+        );
+    }
+
+    #[test]
+    fn absolute_zero() {
+        assert_eq!(t("-273.15C"), t("0K"));
+        assert_eq!(t("-459.67F"), t("0K"));
+        assert_eq!(
+            "-273.16C".parse::<Temperature>(),
+            Err(TemperatureError::BelowAbsoluteZero("-273.16C".to_string()))
+        );
+        assert!("-459.68F".parse::<Temperature>().is_err());
+        assert!("-0.01K".parse::<Temperature>().is_err());
+        assert_eq!(
+            Temperature::new(-300.0, Scale::Celsius)
+                .unwrap_err()
+                .to_string(),
+            "-300C is below absolute zero"
+        );
+        assert!(Temperature::new(f64::NAN, Scale::Kelvin).is_err());
+    }
+
+    #[test]
+    fn exact_conversions() {
+        assert_eq!(t("36.6C").to(Scale::Fahrenheit).to_string(), "97.88F");
+        assert_eq!(t("100C").to(Scale::Kelvin).to_string(), "373.15K");
+        assert_eq!(t("-40C").to(Scale::Fahrenheit).to_string(), "-40F");
+        assert_eq!(t("98F").degrees(Scale::Celsius), 110.0 / 3.0);
+        assert_eq!(t("32F").degrees(Scale::Celsius), 0.0);
+
+        // 所有的读数在三种温标之间转换以后再转换回来，输出完全相同
+        for hundredths in (-27315..50000).step_by(7) {
+            let celsius = Temperature::from_hundredths(hundredths, Scale::Celsius).unwrap();
+            for scale in Scale::ALL {
+                let back = celsius.to(scale).to(Scale::Celsius);
+                assert_eq!(back.to_string(), celsius.to_string());
+                assert_eq!(back.to_string().parse::<Temperature>(), Ok(celsius));
+            }
+        }
+    }
+
+    #[test]
+    fn compare_across_scales() {
+        assert_eq!(t("0C"), t("32F"));
+        assert_eq!(t("0C"), t("273.15K"));
+        assert!(t("100C") > t("200F"));
+        assert!(t("36.6C") < t("98F"));
+        assert!(t("1K") < t("-272C"));
+        let mut readings = [t("300K"), t("20C"), t("70F")];
+        readings.sort();
+        assert_eq!(readings.map(|t| t.to_string()), ["20C", "70F", "300K"]);
+    }
+}
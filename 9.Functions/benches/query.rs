@@ -0,0 +1,135 @@
+//! 比较 `query.rs` 中的查询操作和手写的循环，使用 `cargo bench` 运行
+//!
+//! 这个包只有 `main.rs` 没有 `lib.rs`，所以直接把 `query.rs` 作为模块引入。
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// `cargo clippy --all-targets` 会带着 `cfg(test)` 检查基准测试，但是没有测试框架运行里面的 `#[test]`，
+// 所以 `query.rs` 的测试模块中的 `use` 会被当做没有用到
+#[path = "../src/query.rs"]
+#[allow(unused_imports)]
+mod query;
+
+use query::Query;
+
+const N: u64 = 100_000;
+
+// 运行 `f` 直到总时间超过 0.5 秒，输出平均每次的耗时
+fn bench<R>(name: &str, mut f: impl FnMut() -> R) -> Duration {
+    // 预热
+    black_box(f());
+    let start = Instant::now();
+    let mut iterations = 0;
+    while start.elapsed() < Duration::from_millis(500) {
+        black_box(f());
+        iterations += 1;
+    }
+    let per_iteration = start.elapsed() / iterations;
+    println!("{:<28} {:>12?}", name, per_iteration);
+    per_iteration
+}
+
+fn compare<A, B>(name: &str, query: impl FnMut() -> A, imperative: impl FnMut() -> B) {
+    let query = bench(&format!("{} (query)", name), query);
+    let imperative = bench(&format!("{} (loop)", name), imperative);
+    println!(
+        "{:<28} {:>11.2}x\n",
+        "",
+        query.as_secs_f64() / imperative.as_secs_f64()
+    );
+}
+
+fn data() -> impl Iterator<Item = u64> {
+    // 和 `higher_order_functions` 中一样的平方数，再打乱一下顺序
+    (0..N).map(|n| (n * n) % 1_000_003)
+}
+
+fn main() {
+    compare(
+        "group_by",
+        || data().group_by(|n| n % 7 < 3).count(),
+        || {
+            let mut groups = 0;
+            let mut last = None;
+            for n in data() {
+                let key = n % 7 < 3;
+                if last != Some(key) {
+                    groups += 1;
+                    last = Some(key);
+                }
+            }
+            groups
+        },
+    );
+
+    compare(
+        "window",
+        || data().window(4).filter(|w| w[0] < w[3]).count(),
+        || {
+            let mut buffer = VecDeque::with_capacity(4);
+            let mut count = 0;
+            for n in data() {
+                if buffer.len() == 4 {
+                    buffer.pop_front();
+                }
+                buffer.push_back(n);
+                if buffer.len() == 4 && buffer[0] < buffer[3] {
+                    count += 1;
+                }
+            }
+            count
+        },
+    );
+
+    compare(
+        "dedup_by_key",
+        || data().dedup_by_key(|n| n / 1000).count(),
+        || {
+            let mut count = 0;
+            let mut last = None;
+            for n in data() {
+                let key = n / 1000;
+                if last != Some(key) {
+                    count += 1;
+                    last = Some(key);
+                }
+            }
+            count
+        },
+    );
+
+    compare(
+        "top_k",
+        || data().top_k(10),
+        || {
+            // 和 `top_k` 一样使用大小为 10 的最小堆
+            let mut heap = BinaryHeap::with_capacity(11);
+            for n in data() {
+                if heap.len() < 10 {
+                    heap.push(Reverse(n));
+                } else if n > heap.peek().unwrap().0 {
+                    heap.pop();
+                    heap.push(Reverse(n));
+                }
+            }
+            let mut top: Vec<_> = heap.into_iter().map(|Reverse(n)| n).collect();
+            top.sort_unstable_by(|a, b| b.cmp(a));
+            top
+        },
+    );
+
+    compare(
+        "count_by",
+        || data().count_by(|n| n % 10),
+        || {
+            let mut counts = HashMap::new();
+            for n in data() {
+                *counts.entry(n % 10).or_insert(0) += 1;
+            }
+            counts
+        },
+    );
+}
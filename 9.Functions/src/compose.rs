@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// 组合两个函数，`compose(f, g)(x)` 等于 `f(g(x))`，也就是先调用 `g` 再调用 `f`
+///
+/// 和 `apply_to_3` 一样，闭包只能通过泛型约束来接收，返回值也是一个闭包，
+/// 所以使用 `impl Fn` 来约束返回值的类型。
+pub fn compose<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> C
+where
+    F: Fn(B) -> C,
+    G: Fn(A) -> B,
+{
+    move |x| f(g(x))
+}
+
+/// 按照从左到右的顺序组合多个函数，`pipe!(f, g, h)(x)` 等于 `h(g(f(x)))`
+///
+/// ```ignore
+/// let f = pipe!(|x: i32| x + 1, |x| x * 2, |x: i32| x.to_string());
+/// assert_eq!(f(3), "8");
+/// ```
+macro_rules! pipe {
+    ($f:expr $(,)?) => {
+        $f
+    };
+    ($f:expr, $($rest:expr),+ $(,)?) => {
+        // 先调用 `$f`，再调用剩下的函数组合起来的函数
+        $crate::compose::compose(pipe!($($rest),+), $f)
+    };
+}
+
+/// 把两个参数的函数变成一次接收一个参数的函数，`curry(f)(a)(b)` 等于 `f(a, b)`
+///
+/// 返回的每一个闭包都可以调用多次，所以第一个参数需要实现 `Clone`，
+/// `f` 使用 `Rc` 在所有返回的闭包之间共享。
+pub fn curry<A, B, C, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> C>
+where
+    A: Clone + 'static,
+    F: Fn(A, B) -> C + 'static,
+{
+    let f = Rc::new(f);
+    move |a| {
+        let f = Rc::clone(&f);
+        Box::new(move |b| f(a.clone(), b))
+    }
+}
+
+/// 固定两个参数的函数的第一个参数，`partial(f, a)(b)` 等于 `f(a, b)`
+pub fn partial<A, B, C, F>(f: F, a: A) -> impl Fn(B) -> C
+where
+    A: Clone,
+    F: Fn(A, B) -> C,
+{
+    move |b| f(a.clone(), b)
+}
+
+/// 缓存函数的结果，相同的参数只会调用 `f` 一次
+///
+/// 因为需要修改内部的缓存，所以返回的闭包是 `FnMut`。
+pub fn memoize<A, B, F>(f: F) -> impl FnMut(A) -> B
+where
+    A: Hash + Eq + Clone,
+    B: Clone,
+    F: Fn(A) -> B,
+{
+    let mut cache = HashMap::new();
+    move |a: A| {
+        if let Some(b) = cache.get(&a) {
+            return B::clone(b);
+        }
+        let b = f(a.clone());
+        cache.insert(a, b.clone());
+        b
+    }
+}
+
+/// 把 `FnOnce` 包装成可以调用多次的 `FnMut`，只有第一次调用会执行 `f`，之后都返回 `None`
+///
+/// 编译器在编译的时候就会保证 `FnOnce` 只被调用一次，但是有时候函数需要保存在结构体中，
+/// 或者调用的次数只有运行的时候才知道，这个时候可以在运行时检查。
+pub fn once<R, F>(f: F) -> impl FnMut() -> Option<R>
+where
+    F: FnOnce() -> R,
+{
+    let mut f = Some(f);
+    // `Option::take` 取出闭包以后留下 `None`，所以闭包只会被调用一次
+    move || f.take().map(|f| f())
+}
+
+/// 和 [`once`] 一样，但是第二次调用的时候直接 `panic!`
+pub fn once_or_panic<R, F>(f: F) -> impl FnMut() -> R
+where
+    F: FnOnce() -> R,
+{
+    let mut f = once(f);
+    move || f().expect("a `FnOnce` function was called more than once")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn compose_and_pipe() {
+        let add_one = |x: i32| x + 1;
+        let double = |x: i32| x * 2;
+        assert_eq!(compose(add_one, double)(5), 11);
+        assert_eq!(compose(double, add_one)(5), 12);
+
+        let f = pipe!(add_one, double, |x: i32| x.to_string());
+        assert_eq!(f(3), "8");
+        assert_eq!(pipe!(str::len)("four"), 4);
+        assert_eq!(pipe!(str::trim, str::len,)("  ab "), 2);
+    }
+
+    #[test]
+    fn curry_and_partial() {
+        let add = |a: i32, b: i32| a + b;
+        let curried = curry(add);
+        let add_ten = curried(10);
+        assert_eq!(add_ten(5), 15);
+        assert_eq!(add_ten(-10), 0);
+        assert_eq!(curried(1)(2), 3);
+
+        let greet = partial(
+            |greeting: String, name: &str| format!("{}, {}!", greeting, name),
+            "Hello".to_string(),
+        );
+        assert_eq!(greet("Ferris"), "Hello, Ferris!");
+        assert_eq!(greet("Rust"), "Hello, Rust!");
+    }
+
+    #[test]
+    fn memoize_calls_once_per_argument() {
+        let calls = Cell::new(0);
+        let mut square = memoize(|x: u64| {
+            calls.set(calls.get() + 1);
+            x * x
+        });
+        assert_eq!(square(4), 16);
+        assert_eq!(square(4), 16);
+        assert_eq!(square(5), 25);
+        assert_eq!(calls.get(), 2);
+
+        let mut shout = memoize(|s: String| s.to_uppercase());
+        assert_eq!(shout("hi".to_string()), "HI");
+    }
+
+    #[test]
+    fn once_wrappers() {
+        let text = String::from("FnOnce");
+        let mut f = once(move || text);
+        assert_eq!(f(), Some("FnOnce".to_string()));
+        assert_eq!(f(), None);
+
+        let mut g = once_or_panic(|| 42);
+        assert_eq!(g(), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "called more than once")]
+    fn once_or_panic_panics_on_second_call() {
+        let mut f = once_or_panic(|| ());
+        f();
+        f();
+    }
+}
@@ -0,0 +1,168 @@
+use std::borrow::Cow;
+use std::io;
+use std::ops::RangeInclusive;
+
+// 一条规则：满足条件的时候输出对应的单词
+enum Rule<'a> {
+    // 能被除数整除的时候输出单词
+    Divisor(u64, Cow<'a, str>),
+    // 自定义的条件，闭包返回 `Some` 的时候输出返回的单词
+    Predicate(Box<dyn Fn(u64) -> Option<&'a str> + 'a>),
+}
+
+/// 可以自定义规则的 FizzBuzz
+///
+/// 每个数字按照添加的顺序检查所有的规则，把满足的规则的单词连在一起输出，
+/// 一条规则都不满足的时候输出数字本身。
+///
+/// ```ignore
+/// let fizzbuzz = FizzBuzz::new().rule(3, "fizz").rule(5, "buzz");
+/// assert_eq!(fizzbuzz.word(15), "fizzbuzz");
+/// ```
+#[derive(Default)]
+pub struct FizzBuzz<'a> {
+    rules: Vec<Rule<'a>>,
+}
+
+impl<'a> FizzBuzz<'a> {
+    /// 没有任何规则，所有的数字都原样输出
+    pub fn new() -> FizzBuzz<'a> {
+        FizzBuzz { rules: Vec::new() }
+    }
+
+    /// 经典的规则，3 的倍数是 `fizz`，5 的倍数是 `buzz`
+    pub fn classic() -> FizzBuzz<'a> {
+        FizzBuzz::new().rule(3, "fizz").rule(5, "buzz")
+    }
+
+    /// 能被 `divisor` 整除的时候输出 `word`，和 `is_divisible_by` 一样，0 不能整除任何数字
+    pub fn rule(mut self, divisor: u64, word: impl Into<Cow<'a, str>>) -> FizzBuzz<'a> {
+        self.rules.push(Rule::Divisor(divisor, word.into()));
+        self
+    }
+
+    /// 使用闭包作为条件，闭包返回 `Some(word)` 的时候输出 `word`
+    pub fn predicate<F>(mut self, predicate: F) -> FizzBuzz<'a>
+    where
+        F: Fn(u64) -> Option<&'a str> + 'a,
+    {
+        self.rules.push(Rule::Predicate(Box::new(predicate)));
+        self
+    }
+
+    /// 数字对应的输出
+    ///
+    /// 只有一条规则满足的时候直接借用这条规则的单词，不需要分配新的字符串。
+    pub fn word(&self, n: u64) -> Cow<'_, str> {
+        let mut output: Option<Cow<'_, str>> = None;
+        for rule in &self.rules {
+            let word = match rule {
+                Rule::Divisor(divisor, word) if *divisor != 0 && n.is_multiple_of(*divisor) => {
+                    &**word
+                }
+                Rule::Predicate(predicate) => match predicate(n) {
+                    Some(word) => word,
+                    None => continue,
+                },
+                Rule::Divisor(..) => continue,
+            };
+            output = Some(match output {
+                None => Cow::Borrowed(word),
+                Some(previous) => Cow::Owned(previous.into_owned() + word),
+            });
+        }
+        output.unwrap_or_else(|| Cow::Owned(n.to_string()))
+    }
+
+    /// 依次返回区间中每个数字的输出，不会打印任何东西
+    pub fn iter(&self, range: RangeInclusive<u64>) -> Iter<'_, 'a> {
+        Iter {
+            fizzbuzz: self,
+            range,
+        }
+    }
+
+    /// 把区间中每个数字的输出写到 `out` 中，每个数字一行
+    pub fn write_to<W: io::Write>(
+        &self,
+        range: RangeInclusive<u64>,
+        out: &mut W,
+    ) -> io::Result<()> {
+        for word in self.iter(range) {
+            writeln!(out, "{}", word)?;
+        }
+        Ok(())
+    }
+}
+
+/// [`FizzBuzz::iter`] 返回的迭代器
+pub struct Iter<'f, 'a> {
+    fizzbuzz: &'f FizzBuzz<'a>,
+    range: RangeInclusive<u64>,
+}
+
+impl<'f> Iterator for Iter<'f, '_> {
+    type Item = Cow<'f, str>;
+
+    fn next(&mut self) -> Option<Cow<'f, str>> {
+        let n = self.range.next()?;
+        Some(self.fizzbuzz.word(n))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_rules() {
+        let fizzbuzz = FizzBuzz::classic();
+        let words: Vec<_> = fizzbuzz.iter(1..=15).collect();
+        assert_eq!(
+            words,
+            [
+                "1", "2", "fizz", "4", "buzz", "fizz", "7", "8", "fizz", "buzz", "11", "fizz",
+                "13", "14", "fizzbuzz"
+            ]
+        );
+    }
+
+    #[test]
+    fn borrows_single_words() {
+        let fizzbuzz = FizzBuzz::classic();
+        assert!(matches!(fizzbuzz.word(3), Cow::Borrowed("fizz")));
+        assert!(matches!(fizzbuzz.word(15), Cow::Owned(_)));
+        assert!(matches!(fizzbuzz.word(7), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn custom_rules_and_predicates() {
+        let zero = String::from("zero");
+        let fizzbuzz = FizzBuzz::new()
+            .rule(3, "Fizz")
+            .rule(5, String::from("Buzz"))
+            .rule(7, "Bazz")
+            // 除数是 0 的规则永远不会满足
+            .rule(0, "never")
+            .predicate(|n| n.to_string().contains('3').then_some("Three"))
+            .predicate(|n| (n == 0).then_some(zero.as_str()));
+
+        assert_eq!(fizzbuzz.word(0), "FizzBuzzBazzzero");
+        assert_eq!(fizzbuzz.word(13), "Three");
+        assert_eq!(fizzbuzz.word(30), "FizzBuzzThree");
+        assert_eq!(fizzbuzz.word(105), "FizzBuzzBazz");
+        assert_eq!(fizzbuzz.word(11), "11");
+        assert_eq!(FizzBuzz::new().word(3), "3");
+    }
+
+    #[test]
+    fn stream_to_writer() {
+        let mut out = Vec::new();
+        FizzBuzz::classic().write_to(9..=12, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "fizz\nbuzz\n11\nfizz\n");
+    }
+}
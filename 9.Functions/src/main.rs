@@ -1,4 +1,13 @@
-#![feature(never_type)] // 强制启用内置的 `!` 类型
+// 放宽原书示例中故意保留的写法触发的检查，见根目录的 `Cargo.toml`
+#![allow(
+    unused_mut,
+    clippy::manual_contains,
+    clippy::manual_is_multiple_of,
+    clippy::redundant_field_names,
+    clippy::unused_unit,
+    clippy::useless_vec
+)]
+// #![feature(never_type)] // 强制启用内置的 `!` 类型，只能在 nightly 版本的编译器中使用
 
 //!
 //! # Functions
@@ -9,6 +18,16 @@
 //! 也可以使用 `return` 关键字在代码的任意位置（if\match\loop\等等）让函数提前退出并返回值
 //!
 
+// 可以自定义规则的 `FizzBuzz` 放在了单独的 `fizzbuzz.rs` 文件中
+mod fizzbuzz;
+
+// 组合函数的工具函数放在了单独的 `compose.rs` 文件中，`#[macro_use]` 让 `pipe!` 宏可以在后面使用
+#[macro_use]
+mod compose;
+
+// 迭代器上的查询操作放在了单独的 `query.rs` 文件中
+mod query;
+
 fn functions() {
     // 返回 bool 值的函数
     fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
@@ -43,6 +62,20 @@ fn functions() {
     }
 
     fizzbuzz_to(100);
+
+    // 上面的规则是写死的，而且直接打印了结果，`FizzBuzz` 的规则可以自定义，结果是一个迭代器
+    let custom = fizzbuzz::FizzBuzz::classic()
+        .rule(7, "bazz")
+        .predicate(|n| (n % 10 == 1).then_some("!"));
+    let words: Vec<_> = custom.iter(1..=21).collect();
+    println!("{}", words.join(" "));
+
+    // 也可以直接写到任意实现了 `io::Write` 的地方
+    let mut buffer = Vec::new();
+    fizzbuzz::FizzBuzz::classic()
+        .write_to(13..=15, &mut buffer)
+        .unwrap();
+    print!("{}", String::from_utf8_lossy(&buffer));
 }
 
 /// `关联函数` 和 `关联方法` 这两个概念非常相似
@@ -501,6 +534,37 @@ fn as_output_parameters() {
     // 下面这行代码会报错，因为 `fn_once` 的约束是 `FnOnce` 所以只能调用一次
     // fn_once();
     // 移除注释查看错误
+
+    // 闭包可以作为参数，也可以作为返回值，所以可以写出接收函数并返回新函数的函数
+    let add_one = |x: i32| x + 1;
+    let double = |x: i32| x * 2;
+    println!(
+        "compose(add_one, double)(5) = {}",
+        compose::compose(add_one, double)(5)
+    );
+    let describe = pipe!(add_one, double, |x: i32| format!("result: {}", x));
+    println!("{}", describe(5));
+
+    let power = |base: u64, exp: u32| base.pow(exp);
+    let powers_of_two = compose::curry(power)(2);
+    let cube = compose::partial(|exp: u32, base: u64| base.pow(exp), 3);
+    println!("2^10 = {}, 3^3 = {}", powers_of_two(10), cube(3));
+
+    // 缓存结果，相同的参数只会计算一次
+    let mut slow_square = compose::memoize(|x: u64| {
+        println!("computing {}^2", x);
+        x * x
+    });
+    println!("{} {} {}", slow_square(12), slow_square(12), slow_square(3));
+
+    // 在运行时保证只调用一次
+    let mut fn_once = compose::once(create_fnonce());
+    println!("{:?} {:?}", fn_once(), fn_once());
+    // 调用第二次的时候 `panic!`
+    let mut greeting = compose::once_or_panic(|| "hello".to_uppercase());
+    println!("{}", greeting());
+    // greeting();
+    // TODO ^ 移除注释查看 `panic!` 的信息
 }
 
 /// 标准库 `Iterator::any`
@@ -658,6 +722,35 @@ fn higher_order_functions() {
             .sum(); // 统计所有符合条件的数字数量
         println!("functional style: {}", sum_of_squared_odd_numbers);
     }
+
+    // `Query` 特性给所有的迭代器增加了更多的查询操作，和上面的写法一样可以串联起来
+    {
+        use query::Query;
+
+        let squares = || {
+            (0..)
+                .map(|n| n * n)
+                .take_while(|&n_squared| n_squared < upper)
+        };
+
+        // 按照个位数统计数量
+        let mut counts: Vec<_> = squares().count_by(|n| n % 10).into_iter().collect();
+        counts.sort();
+        println!("last digits: {:?}", counts);
+
+        // 最大的三个平方奇数
+        println!("top 3 odd: {:?}", squares().filter(|&n| is_odd(n)).top_k(3));
+
+        // 相邻的两个平方数的差
+        let differences: Vec<_> = squares().window(2).map(|w| w[1] - w[0]).take(5).collect();
+        println!("differences: {:?}", differences);
+
+        // 按照位数分组，每组只保留百位数字不同的数
+        for (digits, group) in squares().group_by(|n| n.to_string().len()) {
+            let group: Vec<_> = group.into_iter().dedup_by_key(|n| n / 100).collect();
+            println!("{} digit(s): {:?}", digits, group);
+        }
+    }
 }
 
 /// 分流函数是一个特殊的函数，该函数的返回值定义是 `!`，
@@ -716,31 +809,21 @@ fn diverging_functions() {
     // 还有比如说 `loop` 关键字也不会返回值，还有 `exit` 方法不会返回值，或者说一个无限循环的函数也不会返回值，等等的一些场景。
 }
 
+rbe::sections! {
+    functions: "常规函数",
+    associated_functions_and_methods: "关联函数、关联方法",
+    closures: "基础闭包",
+    closures_capturing: "闭包捕获以及闭包的类型推断",
+    as_input_parameters: "闭包当做参数使用",
+    type_anonymity: "闭包捕获的原理",
+    input_functions: "普通函数当做参数传递",
+    as_output_parameters: "闭包当做返回值使用",
+    iterator_any: "标准库的闭包例子 `Iterator::any`",
+    searching_through_iterators: "标准库的闭包例子 `Iterator::find`",
+    higher_order_functions: "高阶函数",
+    diverging_functions: "分流函数 never_type",
+}
+
 fn main() {
-    // 常规函数
-    functions();
-
-    // 关联函数、关联方法
-    associated_functions_and_methods();
-
-    // 基础闭包
-    closures();
-    // 闭包捕获以及闭包的类型推断
-    closures_capturing();
-    // 闭包捕获的原理
-    type_anonymity();
-    // 普通函数当做参数传递
-    input_functions();
-    // 闭包当做返回值使用
-    as_output_parameters();
-
-    // 标准库的一些闭包例子
-    iterator_any();
-    searching_through_iterators();
-
-    // 高阶函数
-    higher_order_functions();
-
-    // 分流函数 never_type
-    diverging_functions();
+    rbe::run(SECTIONS);
 }
@@ -0,0 +1,264 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::iter::Peekable;
+
+/// 迭代器上的查询操作，所有的迭代器都自动实现了这个特性
+///
+/// 返回迭代器的操作都是惰性的，只有在遍历的时候才会从原来的迭代器中取出需要的元素，
+/// 所以也可以用在 `(0..)` 这样的无限迭代器上；但是 `group_by` 每次会把一整组元素收集到 `Vec` 中，
+/// 只有组和组之间是惰性的。`top_k` 和 `count_by` 需要看到所有的元素，所以会立即执行。
+pub trait Query: Iterator + Sized {
+    /// 把连续的、`key` 相同的元素分成一组，返回 `(key, 这一组的元素)`
+    ///
+    /// 和 SQL 的 `GROUP BY` 不同，不相邻的相同 `key` 会分到不同的组中，需要的话可以先排序。
+    ///
+    /// 每一组的元素会全部保存在 `Vec` 中，取出下一组的时候才会继续遍历原来的迭代器。
+    /// 所以一组的元素不能是无限的，例如 `(0..).group_by(|_| 0).next()` 永远不会返回。
+    fn group_by<K, F>(self, key: F) -> GroupBy<Self, F>
+    where
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        GroupBy {
+            iter: self.peekable(),
+            key,
+        }
+    }
+
+    /// 长度为 `size` 的滑动窗口，和切片的 `windows` 一样，但是可以用于任意的迭代器
+    ///
+    /// `size` 是 0 的时候会 `panic!`。
+    fn window(self, size: usize) -> Window<Self>
+    where
+        Self::Item: Clone,
+    {
+        assert!(size != 0, "window size must be non-zero");
+        Window {
+            iter: self,
+            size,
+            buffer: VecDeque::with_capacity(size),
+        }
+    }
+
+    /// 去掉连续的、`key` 相同的元素，只保留第一个
+    fn dedup_by_key<K, F>(self, key: F) -> DedupByKey<Self, F, K>
+    where
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        DedupByKey {
+            iter: self,
+            key,
+            last: None,
+        }
+    }
+
+    /// 最大的 `k` 个元素，从大到小排列
+    ///
+    /// 使用一个大小为 `k` 的最小堆，只需要 `O(k)` 的额外空间，
+    /// 不需要像排序那样先把所有的元素收集起来。
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        if k == 0 {
+            return Vec::new();
+        }
+        // `Reverse` 把标准库的最大堆变成最小堆，堆顶是目前留下的最小的元素
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            if heap.len() < k {
+                heap.push(Reverse(item));
+            } else if let Some(mut smallest) = heap.peek_mut() {
+                if item > smallest.0 {
+                    *smallest = Reverse(item);
+                }
+            }
+        }
+        // 最小堆按照升序排列的结果正好是原来的元素从大到小
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// 统计每个 `key` 出现的次数
+    fn count_by<K, F>(self, mut key: F) -> HashMap<K, usize>
+    where
+        K: Hash + Eq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let mut counts = HashMap::new();
+        for item in self {
+            *counts.entry(key(&item)).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl<I: Iterator> Query for I {}
+
+/// [`Query::group_by`] 返回的迭代器
+pub struct GroupBy<I: Iterator, F> {
+    iter: Peekable<I>,
+    key: F,
+}
+
+impl<I, F, K> Iterator for GroupBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let key = (self.key)(&first);
+        let mut group = vec![first];
+        // 只向后看一个元素，`key` 不同的元素留给下一组
+        while let Some(item) = self.iter.next_if(|item| (self.key)(item) == key) {
+            group.push(item);
+        }
+        Some((key, group))
+    }
+}
+
+/// [`Query::window`] 返回的迭代器
+pub struct Window<I: Iterator> {
+    iter: I,
+    size: usize,
+    buffer: VecDeque<I::Item>,
+}
+
+impl<I> Iterator for Window<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 第一次需要先填满窗口，之后每次只需要移动一个元素
+        if self.buffer.len() == self.size {
+            self.buffer.pop_front();
+        }
+        while self.buffer.len() < self.size {
+            self.buffer.push_back(self.iter.next()?);
+        }
+        Some(self.buffer.iter().cloned().collect())
+    }
+}
+
+/// [`Query::dedup_by_key`] 返回的迭代器
+pub struct DedupByKey<I, F, K> {
+    iter: I,
+    key: F,
+    last: Option<K>,
+}
+
+impl<I, F, K> Iterator for DedupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            let key = (self.key)(&item);
+            if self.last.as_ref() != Some(&key) {
+                self.last = Some(key);
+                return Some(item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn group_by_consecutive_keys() {
+        let groups: Vec<_> = [1, 3, 5, 2, 4, 7, 9, 6]
+            .into_iter()
+            .group_by(|n| n % 2)
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                (1, vec![1, 3, 5]),
+                (0, vec![2, 4]),
+                (1, vec![7, 9]),
+                (0, vec![6])
+            ]
+        );
+        assert_eq!(std::iter::empty::<i32>().group_by(|n| *n).count(), 0);
+    }
+
+    #[test]
+    fn windows() {
+        let windows: Vec<_> = (1..=5).window(3).collect();
+        assert_eq!(windows, [vec![1, 2, 3], vec![2, 3, 4], vec![3, 4, 5]]);
+        assert_eq!((1..=2).window(3).count(), 0);
+        assert_eq!((1..=3).window(1).count(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn zero_window_panics() {
+        let _ = (1..=3).window(0);
+    }
+
+    #[test]
+    fn dedup() {
+        let words = ["apple", "avocado", "banana", "blueberry", "apricot"];
+        let firsts: Vec<_> = words
+            .into_iter()
+            .dedup_by_key(|w| w.chars().next())
+            .collect();
+        assert_eq!(firsts, ["apple", "banana", "apricot"]);
+    }
+
+    #[test]
+    fn top_k_with_heap() {
+        let numbers = [5, 1, 9, 3, 9, 7, 2];
+        assert_eq!(numbers.into_iter().top_k(3), [9, 9, 7]);
+        assert_eq!(numbers.into_iter().top_k(0), Vec::<i32>::new());
+        assert_eq!(numbers.into_iter().top_k(100).len(), numbers.len());
+        assert_eq!(["b", "c", "a"].into_iter().top_k(2), ["c", "b"]);
+    }
+
+    #[test]
+    fn count_by_key() {
+        let counts = ["one", "two", "three", "four", "five"]
+            .into_iter()
+            .count_by(|w| w.len());
+        assert_eq!(counts[&3], 2);
+        assert_eq!(counts[&4], 2);
+        assert_eq!(counts[&5], 1);
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn adapters_are_lazy() {
+        // 在无限迭代器上使用，只会取出需要的元素
+        let pulled = Cell::new(0);
+        let source = (0..).inspect(|_| pulled.set(pulled.get() + 1));
+        let first: Vec<_> = source
+            .map(|n| n / 3)
+            .dedup_by_key(|n| *n)
+            .window(2)
+            .group_by(|w| w[0] % 2)
+            .take(2)
+            .collect();
+        assert_eq!(first, [(0, vec![vec![0, 1]]), (1, vec![vec![1, 2]])]);
+        // 第三组的第一个窗口需要看到 3，也就是第 10 个数字
+        assert_eq!(pulled.get(), 10);
+    }
+}
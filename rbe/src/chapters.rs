@@ -0,0 +1,176 @@
+//!
+//! 所有章节示例的目录
+//!
+//! 每个章节都是一个独立的 `cargo` 工程，这里记录了章节的编号、标题和工程所在的目录（相对于仓库根目录）。
+//!
+
+/// 一个章节对应的示例工程
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chapter {
+    /// 章节编号，例如 `1.2.3`
+    pub id: &'static str,
+    /// 章节标题
+    pub title: &'static str,
+    /// 工程目录，相对于仓库根目录
+    pub path: &'static str,
+    /// 是否故意保留了编译错误，这些工程没有加入工作空间
+    pub compile_error: bool,
+}
+
+macro_rules! chapters {
+    ($($id:literal => $title:literal @ $path:literal $(, $flag:ident)?;)*) => {
+        /// 按照书中顺序排列的所有章节
+        pub const CHAPTERS: &[Chapter] = &[
+            $(Chapter {
+                id: $id,
+                title: $title,
+                path: $path,
+                compile_error: chapters!(@flag $($flag)?),
+            },)*
+        ];
+    };
+    (@flag) => { false };
+    (@flag compile_error) => { true };
+}
+
+chapters! {
+    "1.1" => "Comments" @ "1.Hello World/1.1 Comments";
+    "1.2" => "Formatted print" @ "1.Hello World/1.2 Formatted print", compile_error;
+    "1.2.1" => "Debug" @ "1.Hello World/1.2.1 Debug";
+    "1.2.2" => "Display" @ "1.Hello World/1.2.2 Display";
+    "1.2.2.1" => "Testcase: List" @ "1.Hello World/1.2.2.1 Testcase List";
+    "1.2.3" => "Formatting" @ "1.Hello World/1.2.3 Formatting";
+    "2" => "Primitives" @ "2.Primitives/2. Primitives", compile_error;
+    "2.1" => "Literals and operators" @ "2.Primitives/2.1 Literals and operators";
+    "2.2" => "Tuples" @ "2.Primitives/2.2 Tuples";
+    "2.3" => "Arrays and Slices" @ "2.Primitives/2.3 Arrays and Slices";
+    "3.1" => "Structures" @ "3.Custom Types/3.1 structures";
+    "3.2" => "Enums" @ "3.Custom Types/3.2 Eumns";
+    "3.2.1" => "use" @ "3.Custom Types/3.2.1 use";
+    "3.2.2" => "C-like" @ "3.Custom Types/3.2.2 C-like";
+    "3.2.3" => "Testcase: linked-list" @ "3.Custom Types/3.2.3 Testcase linked-list";
+    "3.3" => "constants" @ "3.Custom Types/3.3 constants", compile_error;
+    "4" => "Variable Bindings" @ "4.Variable Bindings/4. Varable Bindings";
+    "4.1" => "Mutability" @ "4.Variable Bindings/4.1 Mutability", compile_error;
+    "4.2" => "Scope and Shadowing" @ "4.Variable Bindings/4.2 Scope and Shadowing", compile_error;
+    "4.3" => "Declare first" @ "4.Variable Bindings/4.3 Declare first", compile_error;
+    "4.4" => "Freezing" @ "4.Variable Bindings/4.4 Freezing", compile_error;
+    "5.1" => "Casting" @ "5.Types/5.1 casting";
+    "5.2" => "Literals" @ "5.Types/5.2 Literals";
+    "5.3" => "Inference" @ "5.Types/5.3 Inference";
+    "5.4" => "Aliasing" @ "5.Types/5.4 Aliasing";
+    "6.1" => "From and Into" @ "6.Conversion/6.1 From and Into";
+    "6.2" => "TryFrom and TryInto" @ "6.Conversion/6.2 TryFrom and TryInto";
+    "6.3" => "To and from Strings" @ "6.Conversion/To and From Strings";
+    "7" => "Expressions" @ "7.Expressions/expressions", compile_error;
+    "8" => "Flow of Control" @ "8.Flow of Control/flow_of_control";
+    "9" => "Functions" @ "9.Functions";
+    "10.1" => "Visibility" @ "10.Modules/10.1 Visibility";
+    "10.2" => "Struct visibility" @ "10.Modules/10.2 Struct visibility";
+    "10.3" => "The use declaration" @ "10.Modules/10.3 The use declaration";
+    "10.4" => "super and self" @ "10.Modules/10.4 super and self";
+    "10.5" => "File hierarchy" @ "10.Modules/10.5 File hierarchy";
+    "12.1" => "Dependencies" @ "12.Cargo/12.1 Dependencies";
+    "12.2" => "Conventions" @ "12.Cargo/12.2 Conventions";
+    "12.3" => "Testing" @ "12.Cargo/12.3 Testing";
+    "12.4" => "Build Scripts" @ "12.Cargo/12.4 Build Scripts";
+    "13" => "Attributes" @ "13.Attributes/13 Attributes";
+    "13.1" => "dead_code" @ "13.Attributes/13.1 dead_code";
+    "13.3" => "cfg" @ "13.Attributes/13.3 cfg";
+    "14" => "Generics" @ "14.Generics/14. generics";
+    "14.1" => "Functions" @ "14.Generics/14.1 Functions";
+    "14.2" => "Implementation" @ "14.Generics/14.2 Implementation";
+    "14.3" => "Traits" @ "14.Generics/14.3 Traits";
+    "14.4" => "Bounds" @ "14.Generics/14.4 Bounds";
+    "14.5" => "Multiple bounds" @ "14.Generics/14.5 Multiple bounds";
+    "14.6" => "Where clauses" @ "14.Generics/14.6 Where clauses";
+    "14.7" => "New Type Idiom" @ "14.Generics/14.7 newtype idiom";
+    "14.8" => "Associated items" @ "14.Generics/14.8 Associated items";
+    "14.9" => "Phantom type parameters" @ "14.Generics/14.9 Phantom type parameters";
+    "15.1" => "RAII" @ "15.Scoping/15.1 RAII";
+    "15.2" => "Ownership and moves" @ "15.Scoping/15.2 Ownership and moves";
+    "15.3" => "Borrowing" @ "15.Scoping/15.3 Borrowing";
+    "15.4" => "Lifetimes" @ "15.Scoping/15.4 Lifetimes";
+    "16" => "Traits" @ "16.Traits";
+    "17" => "macro_rules!" @ "17.macro_rules";
+    "18" => "Error handling" @ "18.Error Handling";
+    "19" => "Std library types" @ "19.Std Library types";
+    "20" => "Std misc" @ "20.Std misc";
+    "21" => "Testing" @ "21.Testing";
+    "22" => "Unsafe Operations" @ "22.Unsafe Operations";
+    "23" => "Compatibility" @ "23.Compatibility";
+    "24.1" => "Documentation" @ "24.Meta/24.1 documentation", compile_error;
+    "24.2" => "Playground" @ "24.Meta/24.2 Playground";
+}
+
+/// 命令行中指定的运行目标，格式为 `<章节编号>[.<示例名>]`
+///
+/// 例如 `18` 表示运行第 18 章的全部示例，`18.wrapping_errors` 表示只运行第 18 章中的 `wrapping_errors` 示例。
+#[derive(Debug, PartialEq, Eq)]
+pub struct Target<'a> {
+    pub chapter: &'static Chapter,
+    pub section: Option<&'a str>,
+}
+
+/// 根据章节编号查找章节
+pub fn find(id: &str) -> Option<&'static Chapter> {
+    CHAPTERS.iter().find(|chapter| chapter.id == id)
+}
+
+/// 解析运行目标
+///
+/// 开头连续的数字部分是章节编号，剩余的部分是示例名称。
+pub fn parse_target(target: &str) -> Option<Target<'_>> {
+    let split = target
+        .char_indices()
+        .find(|&(index, c)| {
+            c == '.' && !target[index + 1..].starts_with(|c: char| c.is_ascii_digit())
+        })
+        .map(|(index, _)| index);
+
+    let (id, section) = match split {
+        Some(index) => (&target[..index], Some(&target[index + 1..])),
+        None => (target, None),
+    };
+
+    let chapter = find(id)?;
+    let section = section.filter(|section| !section.is_empty());
+
+    Some(Target { chapter, section })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn every_chapter_has_a_manifest() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        for chapter in CHAPTERS {
+            assert!(
+                root.join(chapter.path).join("Cargo.toml").is_file(),
+                "missing manifest for chapter {}",
+                chapter.id
+            );
+        }
+    }
+
+    #[test]
+    fn parse_chapter_and_section() {
+        let target = parse_target("18.wrapping_errors").unwrap();
+        assert_eq!(target.chapter.id, "18");
+        assert_eq!(target.section, Some("wrapping_errors"));
+
+        let target = parse_target("1.2.2.1").unwrap();
+        assert_eq!(target.chapter.id, "1.2.2.1");
+        assert_eq!(target.section, None);
+
+        let target = parse_target("20.thread_test_case_map_reduce").unwrap();
+        assert_eq!(target.chapter.path, "20.Std misc");
+        assert_eq!(target.section, Some("thread_test_case_map_reduce"));
+
+        assert!(parse_target("11").is_none());
+        assert!(parse_target("wrapping_errors").is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//!
+//! 章节示例的公共工具
+//!
+//! 每个章节可以通过 [`sections!`] 宏把 `main` 中依次调用的示例函数注册成一个列表，
+//! 这样每个示例都可以单独在命令行中运行，并且每个示例都会自动生成一个 `#[test]` 测试用例。
+//!
+//! ```ignore
+//! rbe::sections! {
+//!     threads: "线程操作",
+//!     // 标记为 `#[manual]` 的示例默认不会运行，只有在命令行中明确指定的时候才会运行
+//!     #[manual]
+//!     #[should_panic]
+//!     panic_: "主动触发异常",
+//! }
+//!
+//! fn main() {
+//!     rbe::run(SECTIONS);
+//! }
+//! ```
+//!
+
+pub mod snapshot;
+
+use std::env;
+use std::process;
+
+/// 章节中的一个示例
+#[derive(Debug, Clone, Copy)]
+pub struct Section {
+    /// 示例的名字，也就是示例函数的名字
+    pub name: &'static str,
+    /// 示例的简单描述
+    pub description: &'static str,
+    /// 示例函数
+    pub run: fn(),
+    /// 是否只在明确指定的时候才运行，例如会 `panic!` 的示例
+    pub manual: bool,
+}
+
+/// 注册当前章节的所有示例，生成 `SECTIONS` 常量和对应的测试用例
+///
+/// 每一项的格式是 `函数名: "描述"`，函数名前面可以添加 `#[manual]` 标记，这样的示例默认不会运行，
+/// 例如会 `panic!` 的示例。其他的属性，例如文档注释、`#[should_panic]` 或者 `#[ignore]`，
+/// 会原样添加到生成的测试用例上，不会影响示例是否默认运行。
+#[macro_export]
+macro_rules! sections {
+    // 属性中是否有 `#[manual]`
+    (@manual) => { false };
+    (@manual # [manual] $($rest:tt)*) => { true };
+    (@manual # $attr:tt $($rest:tt)*) => { $crate::sections!(@manual $($rest)*) };
+
+    // 生成测试用例，去掉 `#[manual]`，其他的属性保存在 `[...]` 中
+    (@test $name:ident [$($kept:tt)*]) => {
+        $($kept)*
+        #[test]
+        fn $name() {
+            super::$name()
+        }
+    };
+    (@test $name:ident [$($kept:tt)*] # [manual] $($rest:tt)*) => {
+        $crate::sections!(@test $name [$($kept)*] $($rest)*);
+    };
+    (@test $name:ident [$($kept:tt)*] # $attr:tt $($rest:tt)*) => {
+        $crate::sections!(@test $name [$($kept)* # $attr] $($rest)*);
+    };
+
+    // 属性按照 `#` 和 `[...]` 两个 `tt` 匹配，这样才能在上面的规则中识别出 `manual`
+    ($($(# $attr:tt)* $name:ident: $description:literal),* $(,)?) => {
+        /// 当前章节的所有示例，按照 `main` 中的运行顺序排列
+        pub const SECTIONS: &[$crate::Section] = &[
+            $($crate::Section {
+                name: stringify!($name),
+                description: $description,
+                run: $name,
+                manual: $crate::sections!(@manual $(# $attr)*),
+            },)*
+        ];
+
+        #[cfg(test)]
+        mod section_tests {
+            $(
+                $crate::sections!(@test $name [] $(# $attr)*);
+            )*
+        }
+    };
+}
+
+/// 根据过滤条件选出需要运行的示例
+///
+/// 过滤条件和示例名字完全相同的时候只选中这一个示例，否则选中所有名字中包含过滤条件的示例，
+/// 没有任何过滤条件的时候选中所有非 `manual` 的示例，返回的示例保持注册时的顺序。
+pub fn select<'a>(sections: &'a [Section], filters: &[&str]) -> Result<Vec<&'a Section>, String> {
+    if filters.is_empty() {
+        return Ok(sections.iter().filter(|section| !section.manual).collect());
+    }
+
+    let matches = |filter: &str, section: &Section| {
+        if sections.iter().any(|section| section.name == filter) {
+            section.name == filter
+        } else {
+            section.name.contains(filter)
+        }
+    };
+
+    if let Some(filter) = filters
+        .iter()
+        .find(|filter| !sections.iter().any(|section| matches(filter, section)))
+    {
+        return Err(format!("no section matches `{}`", filter));
+    }
+
+    Ok(sections
+        .iter()
+        .filter(|section| filters.iter().any(|filter| matches(filter, section)))
+        .collect())
+}
+
+/// 打印所有的示例
+pub fn list(sections: &[Section]) {
+    let width = sections
+        .iter()
+        .map(|section| section.name.len())
+        .max()
+        .unwrap_or(0);
+    for section in sections {
+        let note = if section.manual { " (manual)" } else { "" };
+        println!(
+            "{:<width$}  {}{}",
+            section.name,
+            section.description,
+            note,
+            width = width
+        );
+    }
+}
+
+/// 章节的 `main` 函数入口
+///
+/// 命令行参数中 `--` 之前的参数是过滤条件，`--list` 会列出所有的示例，`--` 之后的参数留给示例自己使用。
+pub fn run(sections: &[Section]) {
+    let args: Vec<String> = env::args().skip(1).take_while(|arg| arg != "--").collect();
+
+    if args.iter().any(|arg| arg == "--list") {
+        list(sections);
+        return;
+    }
+
+    let filters: Vec<&str> = args.iter().map(String::as_str).collect();
+    match select(sections, &filters) {
+        Ok(selected) => {
+            for section in selected {
+                (section.run)();
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            list(sections);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop() {}
+
+    const SECTIONS: &[Section] = &[
+        Section {
+            name: "result",
+            description: "",
+            run: noop,
+            manual: false,
+        },
+        Section {
+            name: "map_for_result",
+            description: "",
+            run: noop,
+            manual: false,
+        },
+        Section {
+            name: "panic_",
+            description: "",
+            run: noop,
+            manual: true,
+        },
+    ];
+
+    fn names(selected: Vec<&Section>) -> Vec<&'static str> {
+        selected.iter().map(|section| section.name).collect()
+    }
+
+    #[test]
+    fn select_defaults_skip_manual() {
+        assert_eq!(
+            names(select(SECTIONS, &[]).unwrap()),
+            ["result", "map_for_result"]
+        );
+    }
+
+    #[test]
+    fn select_prefers_exact_name() {
+        assert_eq!(names(select(SECTIONS, &["result"]).unwrap()), ["result"]);
+        assert_eq!(
+            names(select(SECTIONS, &["for_res"]).unwrap()),
+            ["map_for_result"]
+        );
+        assert_eq!(names(select(SECTIONS, &["panic"]).unwrap()), ["panic_"]);
+    }
+
+    #[test]
+    fn select_rejects_unknown_filter() {
+        assert!(select(SECTIONS, &["result", "threads"]).is_err());
+    }
+
+    mod macro_sections {
+        fn documented() {}
+
+        fn skipped() {}
+
+        fn panics() {
+            panic!("only runs when selected");
+        }
+
+        crate::sections! {
+            /// 文档注释不会让示例变成 `manual`
+            documented: "带有文档注释的示例",
+            #[ignore]
+            skipped: "测试中忽略，但是默认运行",
+            #[manual]
+            #[should_panic(expected = "only runs when selected")]
+            panics: "只有明确指定的时候才运行",
+        }
+
+        #[test]
+        fn only_manual_marker_is_manual() {
+            let manual: Vec<_> = SECTIONS.iter().map(|section| section.manual).collect();
+            assert_eq!(manual, [false, false, true]);
+        }
+    }
+}
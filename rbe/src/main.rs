@@ -0,0 +1,133 @@
+//!
+//! # rbe
+//!
+//! 章节示例的运行工具，可以在仓库根目录直接列出和运行任意一个章节的示例，
+//! 不需要再进入那些带有空格的目录中执行 `cargo run`。
+//!
+//! ```sh
+//! # 列出所有的章节
+//! cargo rbe list
+//! # 运行第 18 章的全部示例
+//! cargo rbe run 18
+//! # 只运行第 18 章中的 `wrapping_errors` 示例
+//! cargo rbe run 18.wrapping_errors
+//! # 更新所有章节的输出快照
+//! cargo rbe bless
+//! ```
+//!
+
+mod chapters;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use chapters::{Target, CHAPTERS};
+
+const USAGE: &str = "\
+Usage:
+    rbe list                           列出所有的章节
+    rbe run <chapter>[.<section>] [..] 运行指定章节的示例，剩余的参数会原样传给示例程序
+    rbe bless [cargo test 参数]        使用当前的输出更新所有章节的快照文件
+    rbe help                           显示帮助信息";
+
+/// 仓库的根目录，也就是 `rbe` 工程的上一级目录
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("`rbe` should live inside the repository")
+        .to_path_buf()
+}
+
+fn list() {
+    for chapter in CHAPTERS {
+        let note = if chapter.compile_error {
+            " (compile error on purpose)"
+        } else {
+            ""
+        };
+        println!(
+            "{:<8} {:<26} {}{}",
+            chapter.id, chapter.title, chapter.path, note
+        );
+    }
+}
+
+/// 使用 `cargo run --manifest-path` 运行章节的工程，返回子进程的退出码
+fn run(target: Target, args: &[String]) -> i32 {
+    // 示例中读写的文件都是相对于章节目录的，所以要在章节目录中运行
+    let dir = workspace_root().join(target.chapter.path);
+    let manifest = dir.join("Cargo.toml");
+
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command
+        .arg("run")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .arg("--")
+        .current_dir(&dir);
+    command.args(target.section);
+    command.args(args);
+
+    match command.status() {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("failed to run cargo: {}", e);
+            1
+        }
+    }
+}
+
+/// 设置 `RBE_BLESS` 运行所有章节的快照测试，用当前的输出覆盖保存的快照
+fn bless(args: &[String]) -> i32 {
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command
+        .args(["test", "--workspace", "--test", "snapshot"])
+        .args(args)
+        .env("RBE_BLESS", "1")
+        .current_dir(workspace_root());
+
+    match command.status() {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("failed to run cargo: {}", e);
+            1
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let code = match args.first().map(String::as_str) {
+        Some("list") => {
+            list();
+            0
+        }
+        Some("run") => match args.get(1) {
+            Some(target) => match chapters::parse_target(target) {
+                Some(target) => run(target, &args[2..]),
+                None => {
+                    eprintln!("unknown chapter `{}`, try `rbe list`", target);
+                    2
+                }
+            },
+            None => {
+                eprintln!("{}", USAGE);
+                2
+            }
+        },
+        Some("bless") => bless(&args[1..]),
+        Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            0
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    };
+
+    process::exit(code);
+}
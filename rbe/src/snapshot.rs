@@ -0,0 +1,291 @@
+//!
+//! 示例输出的快照测试
+//!
+//! 运行章节的可执行文件，把标准输出和 `tests/snapshots/<示例名>.expected` 文件中保存的结果进行对比，
+//! 输出不一致的时候测试失败。设置环境变量 `RBE_BLESS=1` 的时候会用当前的输出覆盖保存的结果。
+//!
+//! ```ignore
+//! // tests/snapshot.rs
+//! use rbe::snapshot::{self, Snapshot};
+//!
+//! #[test]
+//! fn snapshot() {
+//!     Snapshot::sections(env!("CARGO_BIN_EXE_std_misc"))
+//!         // 多线程的输出顺序是不确定的
+//!         .normalize("threads", snapshot::sort_lines)
+//!         .check();
+//! }
+//! ```
+//!
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 保存快照文件的目录，相对于章节目录
+const SNAPSHOT_DIR: &str = "tests/snapshots";
+
+/// 没有注册示例的章节只有一个快照，名字是 `main`
+const MAIN: &str = "main";
+
+type Normalizer = Box<dyn Fn(&str) -> String>;
+
+/// 一个章节的快照测试
+pub struct Snapshot {
+    exe: PathBuf,
+    sectioned: bool,
+    normalizers: HashMap<&'static str, Vec<Normalizer>>,
+}
+
+impl Snapshot {
+    /// 直接运行可执行文件，整个输出保存为一个快照
+    pub fn new(exe: impl Into<PathBuf>) -> Self {
+        Snapshot {
+            exe: exe.into(),
+            sectioned: false,
+            normalizers: HashMap::new(),
+        }
+    }
+
+    /// 通过 `--list` 获取章节中注册的示例，每个示例单独运行并保存为一个快照
+    ///
+    /// 只在明确指定的时候才运行的示例（`manual`）不会生成快照。
+    pub fn sections(exe: impl Into<PathBuf>) -> Self {
+        Snapshot {
+            sectioned: true,
+            ..Snapshot::new(exe)
+        }
+    }
+
+    /// 在对比之前处理指定示例的输出，用来屏蔽或者排序那些不确定的内容
+    ///
+    /// 没有注册示例的章节使用 `main` 作为示例名。
+    pub fn normalize(
+        mut self,
+        section: &'static str,
+        normalizer: impl Fn(&str) -> String + 'static,
+    ) -> Self {
+        self.normalizers
+            .entry(section)
+            .or_default()
+            .push(Box::new(normalizer));
+        self
+    }
+
+    /// 运行所有的示例并对比快照，任意一个快照不一致都会 `panic!`
+    pub fn check(self) {
+        let bless = env::var_os("RBE_BLESS").is_some();
+        let dir = Path::new(SNAPSHOT_DIR);
+        if bless {
+            fs::create_dir_all(dir).expect("failed to create snapshot directory");
+        }
+
+        let names = if self.sectioned {
+            self.list()
+        } else {
+            vec![MAIN.to_string()]
+        };
+
+        for name in self.normalizers.keys() {
+            assert!(
+                names.iter().any(|n| n == name),
+                "normalizer registered for unknown section `{}`",
+                name
+            );
+        }
+
+        let mut failures = Vec::new();
+        for name in &names {
+            let actual = self.output(name);
+            let path = dir.join(format!("{}.expected", name));
+
+            if bless {
+                fs::write(&path, &actual).expect("failed to write snapshot");
+                continue;
+            }
+
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => {
+                    failures.push(format!("{}\n{}", path.display(), diff(&expected, &actual)))
+                }
+                Err(_) => failures.push(format!("{}\n  missing snapshot", path.display())),
+            }
+        }
+
+        // 删除已经不存在的示例留下的快照
+        for stale in self.stale(dir, &names) {
+            if bless {
+                fs::remove_file(&stale).expect("failed to remove stale snapshot");
+            } else {
+                failures.push(format!("{}\n  stale snapshot", stale.display()));
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "snapshot mismatch, rerun with `RBE_BLESS=1` to update:\n\n{}",
+            failures.join("\n\n")
+        );
+    }
+
+    fn run(&self, args: &[&str]) -> String {
+        let output = Command::new(&self.exe)
+            .args(args)
+            .output()
+            .unwrap_or_else(|e| panic!("failed to run {}: {}", self.exe.display(), e));
+        assert!(
+            output.status.success(),
+            "{} {:?} exited with {}\n{}",
+            self.exe.display(),
+            args,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    /// 从 `--list` 的输出中解析示例的名字
+    fn list(&self) -> Vec<String> {
+        self.run(&["--list"])
+            .lines()
+            .filter(|line| !line.ends_with("(manual)"))
+            .filter_map(|line| line.split_whitespace().next())
+            .map(String::from)
+            .collect()
+    }
+
+    fn output(&self, name: &str) -> String {
+        let raw = if self.sectioned {
+            self.run(&[name])
+        } else {
+            self.run(&[])
+        };
+
+        // 可执行文件的路径和机器相关，统一替换掉
+        let mut output = raw.replace(&*self.exe.to_string_lossy(), "[EXE]");
+        for normalize in self.normalizers.get(name).into_iter().flatten() {
+            output = normalize(&output);
+        }
+        output
+    }
+
+    fn stale(&self, dir: &Path, names: &[String]) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut stale: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == "expected")
+                    && !path
+                        .file_stem()
+                        .is_some_and(|stem| names.iter().any(|name| stem == name.as_str()))
+            })
+            .collect();
+        stale.sort();
+        stale
+    }
+}
+
+/// 找到第一行不一样的内容
+fn diff(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (None, None) => return "  trailing newline differs".to_string(),
+            (e, a) => {
+                return format!(
+                    "  line {}\n  - {}\n  + {}",
+                    line,
+                    e.unwrap_or("<end of output>"),
+                    a.unwrap_or("<end of output>")
+                )
+            }
+        }
+    }
+}
+
+/// 对输出的每一行进行排序，用于多线程这类输出顺序不确定的示例
+pub fn sort_lines(output: &str) -> String {
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort_unstable();
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// 对每一行中 `[]` 或者 `{}` 里面用 `, ` 分隔的元素进行排序，用于 `HashSet` 这类元素顺序不确定的输出
+pub fn sort_items(output: &str) -> String {
+    let mut result = String::with_capacity(output.len());
+    let mut rest = output;
+    while let Some(start) = rest.find(['[', '{']) {
+        let close = if rest.as_bytes()[start] == b'[' {
+            ']'
+        } else {
+            '}'
+        };
+        let Some(len) = rest[start + 1..].find(close) else {
+            break;
+        };
+        let mut items: Vec<&str> = rest[start + 1..start + 1 + len].split(", ").collect();
+        items.sort_unstable();
+
+        result.push_str(&rest[..=start]);
+        result.push_str(&items.join(", "));
+        rest = &rest[start + 1 + len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 把以 `prefix` 开头的行剩余的部分替换成 `***`，用于版本号这类和运行环境相关的输出
+pub fn mask(prefix: &'static str) -> impl Fn(&str) -> String {
+    move |output| {
+        output
+            .lines()
+            .map(|line| {
+                if line.starts_with(prefix) {
+                    format!("{}***\n", prefix)
+                } else {
+                    format!("{}\n", line)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_items_in_brackets() {
+        assert_eq!(
+            sort_items("A: {3, 1, 2}\nUnion: [5, 4]\n"),
+            "A: {1, 2, 3}\nUnion: [4, 5]\n"
+        );
+        assert_eq!(sort_items("no brackets [\n"), "no brackets [\n");
+    }
+
+    #[test]
+    fn mask_prefixed_lines() {
+        let mask = mask("rustc 1.");
+        assert_eq!(
+            mask("rustc succeeded\nrustc 1.95.0 (abc)\n"),
+            "rustc succeeded\nrustc 1.***\n"
+        );
+    }
+
+    #[test]
+    fn diff_reports_first_mismatch() {
+        assert_eq!(diff("a\nb\n", "a\nc\n"), "  line 2\n  - b\n  + c");
+        assert_eq!(
+            diff("a\n", "a\nb\n"),
+            "  line 2\n  - <end of output>\n  + b"
+        );
+    }
+}